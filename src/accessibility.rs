@@ -0,0 +1,154 @@
+use crate::{collision::Hitbox, enemy::Enemy, settings::Settings};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(tint_hazards).add_system(outline_hitboxes);
+    }
+}
+
+// Projectiles and tiles that kill the player, tinted according to the colorblind mode
+#[derive(Component, Clone, Copy)]
+pub enum Hazard {
+    Laser,
+    Missile,
+    Trap,
+}
+
+#[derive(Serialize, Deserialize, Display, EnumIter, PartialEq, Eq, Copy, Clone)]
+pub enum ColorblindMode {
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    // Colors from the Okabe-Ito palette, picked so that every hazard stays distinct from the others
+    // and from the background
+    fn get_tint(&self, hazard: Hazard) -> Color {
+        const SKY_BLUE: Color = Color::rgb(0.34, 0.71, 0.91);
+        const ORANGE: Color = Color::rgb(0.9, 0.62, 0.0);
+        const VERMILLION: Color = Color::rgb(0.84, 0.37, 0.0);
+        const BLUISH_GREEN: Color = Color::rgb(0.0, 0.62, 0.45);
+        const REDDISH_PURPLE: Color = Color::rgb(0.8, 0.47, 0.65);
+
+        match (self, hazard) {
+            (ColorblindMode::Off, _) => Color::WHITE,
+            (ColorblindMode::Tritanopia, Hazard::Laser) => VERMILLION,
+            (ColorblindMode::Tritanopia, Hazard::Missile) => BLUISH_GREEN,
+            (_, Hazard::Laser) => SKY_BLUE,
+            (_, Hazard::Missile) => ORANGE,
+            (_, Hazard::Trap) => REDDISH_PURPLE,
+        }
+    }
+}
+
+// Edge of a hitbox, drawn over enemies in high contrast mode
+#[derive(Component, Clone, Copy)]
+struct ContrastOutline;
+
+const OUTLINE_WIDTH: f32 = 2.0;
+// Relative to the outlined entity, so that outlines are drawn above it
+const OUTLINE_Z: f32 = 0.5;
+
+pub fn spawn_hitbox_outline(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    hitbox: Hitbox,
+    color: Color,
+    marker: impl Component + Copy,
+) {
+    let mut spawn_sprite = |size: Vec2, offset: Vec2, texture: Handle<Image>| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..Sprite::default()
+                },
+                texture,
+                transform: Transform::from_translation(offset.extend(OUTLINE_Z)),
+                ..SpriteBundle::default()
+            })
+            .insert(marker);
+    };
+
+    match hitbox {
+        Hitbox::Rectangle(size) => {
+            // One thin sprite along each edge
+            let half = size / 2.0;
+            let horizontal = Vec2::new(size.x + OUTLINE_WIDTH, OUTLINE_WIDTH);
+            let vertical = Vec2::new(OUTLINE_WIDTH, size.y + OUTLINE_WIDTH);
+            spawn_sprite(horizontal, Vec2::new(0.0, half.y), Handle::default());
+            spawn_sprite(horizontal, Vec2::new(0.0, -half.y), Handle::default());
+            spawn_sprite(vertical, Vec2::new(half.x, 0.0), Handle::default());
+            spawn_sprite(vertical, Vec2::new(-half.x, 0.0), Handle::default());
+        }
+        Hitbox::Circle(radius) => spawn_sprite(
+            Vec2::splat(radius * 2.0),
+            Vec2::ZERO,
+            asset_server.load("outline-circle.png"),
+        ),
+        Hitbox::Capsule { length, radius } => {
+            // Straight sides with a circle at each end
+            let half_segment = (length / 2.0 - radius).max(0.0);
+            let side = Vec2::new(OUTLINE_WIDTH, half_segment * 2.0);
+            spawn_sprite(side, Vec2::new(radius, 0.0), Handle::default());
+            spawn_sprite(side, Vec2::new(-radius, 0.0), Handle::default());
+            for end in [half_segment, -half_segment] {
+                spawn_sprite(
+                    Vec2::splat(radius * 2.0),
+                    Vec2::new(0.0, end),
+                    asset_server.load("outline-circle.png"),
+                );
+            }
+        }
+    }
+}
+
+fn tint_hazards(
+    settings: Res<Settings>,
+    mut hazards: Query<(&Hazard, ChangeTrackers<Hazard>, &mut TextureAtlasSprite)>,
+) {
+    for (hazard, tracker, mut sprite) in hazards.iter_mut() {
+        if tracker.is_added() || settings.is_changed() {
+            sprite.color = settings.colorblind_mode.get_tint(*hazard);
+        }
+    }
+}
+
+fn outline_hitboxes(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    enemies: Query<(Entity, &Hitbox, ChangeTrackers<Hitbox>), With<Enemy>>,
+    outlines: Query<Entity, With<ContrastOutline>>,
+) {
+    // Redraw every outline when the settings change
+    if settings.is_changed() {
+        for outline in outlines.iter() {
+            commands.entity(outline).despawn_recursive();
+        }
+    }
+
+    if settings.high_contrast {
+        for (entity, hitbox, tracker) in enemies.iter() {
+            if tracker.is_added() || settings.is_changed() {
+                commands.entity(entity).with_children(|parent| {
+                    spawn_hitbox_outline(
+                        parent,
+                        &asset_server,
+                        *hitbox,
+                        Color::WHITE,
+                        ContrastOutline,
+                    )
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    accessibility::spawn_hitbox_outline,
+    collision::Hitbox,
+    storage,
+    ui::{
+        spawn_back_button,
+        widgets::{
+            spawn_column, spawn_menu_screen, spawn_slider, spawn_text, spawn_title, spawn_toggle,
+            Slider, Toggle, Tooltip,
+        },
+        GameFont,
+    },
+    AppState,
+};
+use benimator::{PlaySpeedMultiplier, SpriteSheetAnimation};
+use bevy::{prelude::*, utils::Duration};
+use serde::{Deserialize, Serialize};
+
+const ASSIST_FILE: &str = "assist.ron";
+
+const HITBOX_COLOR: Color = Color::rgb(1.0, 0.2, 0.8);
+
+pub struct AssistPlugin;
+
+impl Plugin for AssistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssistSettings>()
+            .init_resource::<GameTime>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_game_time)
+            .add_system(show_hitboxes)
+            .add_system(scale_animation_speed)
+            .add_system_set(SystemSet::on_enter(AppState::Assist).with_system(create_assist_menu))
+            .add_system_set(
+                SystemSet::on_update(AppState::Assist).with_system(manage_assist_controls),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Assist).with_system(save_assists));
+    }
+}
+
+// Options that make levels easier, runs that use any of them are recorded separately
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AssistSettings {
+    // Multiplier for the speed of everything in a level, between 0.5 and 1
+    pub game_speed: f32,
+    pub invincible: bool,
+    pub show_hitboxes: bool,
+}
+
+impl AssistSettings {
+    pub fn is_active(&self) -> bool {
+        self.game_speed < 1.0 || self.invincible || self.show_hitboxes
+    }
+
+    fn save(&self) {
+        if let Err(error) = storage::save_config(ASSIST_FILE, self) {
+            warn!("Failed to save assist settings: {error}");
+        }
+    }
+}
+
+impl Default for AssistSettings {
+    fn default() -> Self {
+        Self {
+            game_speed: 1.0,
+            invincible: false,
+            show_hitboxes: false,
+        }
+    }
+}
+
+impl FromWorld for AssistSettings {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load_config(ASSIST_FILE)
+    }
+}
+
+// Time since the last frame scaled by the game speed, used for gameplay timers and movement
+#[derive(Default)]
+pub struct GameTime {
+    delta: Duration,
+}
+
+impl GameTime {
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+}
+
+fn update_game_time(
+    time: Res<Time>,
+    assists: Res<AssistSettings>,
+    mut game_time: ResMut<GameTime>,
+) {
+    game_time.delta = time.delta().mul_f32(assists.game_speed);
+}
+
+// Play sprite animations at the game speed, like everything else in a level
+fn scale_animation_speed(
+    mut commands: Commands,
+    assists: Res<AssistSettings>,
+    animations: Query<Entity, Added<Handle<SpriteSheetAnimation>>>,
+) {
+    for entity in animations.iter() {
+        commands
+            .entity(entity)
+            .insert(PlaySpeedMultiplier::new(assists.game_speed));
+    }
+}
+
+// Outline of a hitbox shown by the show hitboxes assist
+#[derive(Component, Clone, Copy)]
+struct AssistOutline;
+
+fn show_hitboxes(
+    mut commands: Commands,
+    assists: Res<AssistSettings>,
+    asset_server: Res<AssetServer>,
+    hitboxes: Query<(Entity, &Hitbox), Added<Hitbox>>,
+) {
+    if assists.show_hitboxes {
+        for (entity, hitbox) in hitboxes.iter() {
+            commands.entity(entity).with_children(|parent| {
+                spawn_hitbox_outline(parent, &asset_server, *hitbox, HITBOX_COLOR, AssistOutline)
+            });
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum AssistOption {
+    GameSpeed,
+    Invincible,
+    ShowHitboxes,
+}
+
+fn create_assist_menu(mut commands: Commands, font: Res<GameFont>, assists: Res<AssistSettings>) {
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    spawn_back_button(&mut commands, font.get_handle());
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Assist");
+        spawn_column(parent, |parent| {
+            // Game speed is changed in steps of 10%
+            let slider = Slider::new(assists.game_speed, 0.5, 1.0, 0.1);
+            spawn_slider(parent, &font, "Game Speed", slider, AssistOption::GameSpeed)
+                .insert(Tooltip("Slow down projectiles, traps and the bee".into()));
+            spawn_toggle(
+                parent,
+                &font,
+                "Invincibility",
+                assists.invincible,
+                AssistOption::Invincible,
+            )
+            .insert(Tooltip("Fly through walls, traps and projectiles".into()));
+            spawn_toggle(
+                parent,
+                &font,
+                "Show Hitboxes",
+                assists.show_hitboxes,
+                AssistOption::ShowHitboxes,
+            )
+            .insert(Tooltip(
+                "Outline the area of everything that can be touched".into(),
+            ));
+            spawn_text(
+                parent,
+                &font,
+                30.0,
+                [(
+                    "Times set with assists are kept apart from best times and earn no medals"
+                        .to_string(),
+                    Color::WHITE,
+                )],
+            );
+        });
+    });
+}
+
+fn manage_assist_controls(
+    mut assists: ResMut<AssistSettings>,
+    toggles: Query<(&Toggle, &AssistOption), Changed<Toggle>>,
+    sliders: Query<(&Slider, &AssistOption), Changed<Slider>>,
+) {
+    for (toggle, option) in toggles.iter() {
+        match option {
+            AssistOption::Invincible => assists.invincible = toggle.0,
+            AssistOption::ShowHitboxes => assists.show_hitboxes = toggle.0,
+            AssistOption::GameSpeed => {}
+        }
+    }
+    for (slider, option) in sliders.iter() {
+        if let AssistOption::GameSpeed = option {
+            assists.game_speed = slider.value;
+        }
+    }
+}
+
+fn save_assists(assists: Res<AssistSettings>) {
+    assists.save();
+}
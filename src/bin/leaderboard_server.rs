@@ -0,0 +1,115 @@
+// Minimal leaderboard server for testing online leaderboards locally:
+// cargo run --bin leaderboard_server -- [address] [directory]
+use beeline::online::backend::{
+    invalid_data, FileBackend, LeaderboardBackend, Submission, TIMEOUT,
+};
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    process,
+};
+
+// Requests with larger bodies are rejected, which leaves room for over ten minutes of replay
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let address = args.get(1).map_or("127.0.0.1:7878", String::as_str);
+    let directory = args.get(2).map_or("leaderboard", String::as_str);
+    if let Err(error) = run_server(address, &FileBackend::new(directory.into())) {
+        eprintln!("Failed to run leaderboard server: {error}");
+        process::exit(1);
+    }
+}
+
+// Minimal HTTP server for testing, which stores submissions with the given backend:
+// POST /submissions with a submission as the body, GET /levels/<level hash> for the fastest runs
+fn run_server(address: &str, backend: &FileBackend) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    println!("Leaderboard server listening on {address}");
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Failed to accept connection: {error}");
+                continue;
+            }
+        };
+        let (status, body) = match handle_request(&mut stream, backend) {
+            Ok(body) => ("200 OK", body),
+            Err(error) => {
+                let status = match error.kind() {
+                    io::ErrorKind::NotFound => "404 Not Found",
+                    io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => "400 Bad Request",
+                    _ => "500 Internal Server Error",
+                };
+                (status, error.to_string())
+            }
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/ron\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len(),
+        );
+        if let Err(error) = stream.write_all(response.as_bytes()) {
+            eprintln!("Failed to respond: {error}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_request(stream: &mut TcpStream, backend: &FileBackend) -> io::Result<String> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+
+    // Only the length of the body is needed from the headers
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(invalid_data)?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        return Err(invalid_data("Request body is too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).map_err(invalid_data)?;
+
+    match (method, path.strip_prefix("/levels/")) {
+        ("POST", _) if path == "/submissions" => {
+            let submission: Submission = ron::from_str(&body).map_err(invalid_data)?;
+            backend.submit(&submission)?;
+            println!(
+                "{} finished {} in {:.2}",
+                submission.name, submission.level_hash, submission.time
+            );
+            Ok(String::new())
+        }
+        ("GET", Some(level_hash)) => {
+            ron::to_string(&backend.get_top(level_hash)?).map_err(invalid_data)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No route for {method} {path}"),
+        )),
+    }
+}
@@ -1,15 +1,26 @@
 use crate::{
-    player::Player,
+    player::{NearMiss, Player},
+    settings::Settings,
     upgrades::{Upgrade, UpgradeTracker},
+    world::GameWorld,
     AppState,
 };
 use bevy::prelude::*;
+use rand::random;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(SystemSet::on_update(AppState::Game).with_system(follow_player));
+        app.init_resource::<ScreenShake>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(reset_camera_focus))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(shake_on_near_miss)
+                    .with_system(follow_player),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::Death).with_system(shake_on_death))
+            .add_system_set(SystemSet::on_update(AppState::Death).with_system(shake_camera));
     }
 }
 
@@ -18,27 +29,82 @@ pub struct MainCamera;
 
 impl MainCamera {
     const INTERPOLATION: f32 = 0.1;
+    // How far ahead of the player the camera looks, in seconds of movement
+    const LOOK_AHEAD_SECS: f32 = 0.25;
+    const MAX_LOOK_AHEAD: f32 = 80.0;
 }
 
-pub fn spawn_camera(commands: &mut Commands, position: Vec2) {
+// Position the camera is easing towards, kept separately so that screen shake does not build up
+#[derive(Component, Default)]
+struct CameraFocus {
+    position: Vec2,
+    look_ahead: Vec2,
+    last_player_position: Option<Vec2>,
+}
+
+// Shake intensity between 0 and 1, decays over time
+#[derive(Default)]
+struct ScreenShake {
+    trauma: f32,
+    offset: Vec2,
+}
+
+impl ScreenShake {
+    const MAX_OFFSET: f32 = 12.0;
+    const DECAY: f32 = 1.5;
+
+    fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    fn update(&mut self, delta_seconds: f32, enabled: bool) {
+        self.trauma = (self.trauma - Self::DECAY * delta_seconds).max(0.0);
+        // Squaring trauma makes small shakes subtle and large shakes violent
+        let strength = if enabled { self.trauma.powi(2) } else { 0.0 };
+        self.offset = Vec2::new(random::<f32>() * 2.0 - 1.0, random::<f32>() * 2.0 - 1.0)
+            * Self::MAX_OFFSET
+            * strength;
+    }
+}
+
+pub fn spawn_camera(commands: &mut Commands, position: Vec2, scale: f32) {
     let mut orthographic_camera_bundle = OrthographicCameraBundle::new_2d();
-    orthographic_camera_bundle.orthographic_projection.scale = 0.5;
+    orthographic_camera_bundle.orthographic_projection.scale = scale;
 
     orthographic_camera_bundle.transform.translation.x = position.x;
     orthographic_camera_bundle.transform.translation.y = position.y;
 
     commands
         .spawn_bundle(orthographic_camera_bundle)
+        .insert(CameraFocus::default())
         .insert(MainCamera);
 }
 
+// Start easing from wherever the start delay left the camera
+fn reset_camera_focus(mut camera: Query<(&Transform, &mut CameraFocus), With<MainCamera>>) {
+    for (transform, mut focus) in camera.iter_mut() {
+        *focus = CameraFocus {
+            position: transform.translation.truncate(),
+            ..CameraFocus::default()
+        };
+    }
+}
+
 fn follow_player(
-    mut camera_transform: Query<&mut Transform, With<MainCamera>>,
+    time: Res<Time>,
+    world: Res<GameWorld>,
+    settings: Res<Settings>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera: Query<
+        (&mut Transform, &mut CameraFocus, &OrthographicProjection),
+        With<MainCamera>,
+    >,
     player_transform: Query<&Transform, (With<Player>, Without<MainCamera>)>,
     upgrades: Res<UpgradeTracker>,
 ) {
-    let mut camera_transform = camera_transform.single_mut();
-    let player_transform = player_transform.single();
+    let (mut camera_transform, mut focus, projection) = camera.single_mut();
+    let player_position = player_transform.single().translation.truncate();
+    let delta_seconds = time.delta_seconds();
 
     let interpolation = MainCamera::INTERPOLATION
         // Double interpolation if double speed is active
@@ -47,9 +113,62 @@ fn follow_player(
         } else {
             1.0
         };
+    // Interpolation is tuned per frame at 60 fps, adjust it to the actual frame time
+    let interpolation = 1.0 - (1.0 - interpolation).powf(delta_seconds * 60.0);
 
-    camera_transform.translation = player_transform
-        .translation
-        .truncate()
-        .extend(camera_transform.translation.z);
+    // Look ahead in the direction the player is moving, teleports are capped by the maximum
+    let velocity = match focus.last_player_position {
+        Some(last_position) if delta_seconds > 0.0 => {
+            (player_position - last_position) / delta_seconds
+        }
+        _ => Vec2::ZERO,
+    };
+    focus.last_player_position = Some(player_position);
+    let look_ahead =
+        (velocity * MainCamera::LOOK_AHEAD_SECS).clamp_length_max(MainCamera::MAX_LOOK_AHEAD);
+    focus.look_ahead = focus.look_ahead.lerp(look_ahead, interpolation);
+
+    let target = player_position + focus.look_ahead;
+    focus.position = focus.position.lerp(target, interpolation);
+
+    // Keep the view inside the level, centering it on levels smaller than the view
+    let half_view = Vec2::new(projection.right, projection.top) * projection.scale;
+    let (min, max) = world.get_bounds();
+    let clamp_axis = |value: f32, min: f32, max: f32, half_view: f32| {
+        if max - min <= half_view * 2.0 {
+            (min + max) / 2.0
+        } else {
+            value.clamp(min + half_view, max - half_view)
+        }
+    };
+    focus.position = Vec2::new(
+        clamp_axis(focus.position.x, min.x, max.x, half_view.x),
+        clamp_axis(focus.position.y, min.y, max.y, half_view.y),
+    );
+
+    shake.update(delta_seconds, settings.is_screen_shake_enabled());
+    camera_transform.translation =
+        (focus.position + shake.offset).extend(camera_transform.translation.z);
+}
+
+fn shake_on_near_miss(mut shake: ResMut<ScreenShake>, mut near_misses: EventReader<NearMiss>) {
+    for _ in near_misses.iter() {
+        shake.add_trauma(0.3);
+    }
+}
+
+fn shake_on_death(mut shake: ResMut<ScreenShake>) {
+    shake.add_trauma(0.8);
+}
+
+fn shake_camera(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera: Query<(&mut Transform, &CameraFocus), With<MainCamera>>,
+) {
+    shake.update(time.delta_seconds(), settings.is_screen_shake_enabled());
+    for (mut transform, focus) in camera.iter_mut() {
+        transform.translation = (focus.position + shake.offset).extend(transform.translation.z);
+    }
 }
@@ -0,0 +1,136 @@
+use crate::{
+    collision::Hitbox,
+    game_overlay::GameTimer,
+    player::Player,
+    ui::ACTIVE_BUTTON_COLOR,
+    world::{GameWorld, Goal},
+    AppState,
+};
+use bevy::{prelude::*, utils::HashSet};
+
+pub struct CollectiblePlugin;
+
+impl Plugin for CollectiblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunProgress>().add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(reach_checkpoint)
+                .with_system(collect_nectar)
+                .with_system(unlock_goals),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct Checkpoint {
+    pub coordinates: (usize, usize),
+}
+
+impl Checkpoint {
+    pub fn color(reached: bool) -> Color {
+        if reached {
+            ACTIVE_BUTTON_COLOR
+        } else {
+            Color::GRAY
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Nectar {
+    pub coordinates: (usize, usize),
+}
+
+impl Nectar {
+    pub const SIZE: f32 = 16.0;
+}
+
+// Goals with this component cannot be reached until every nectar has been collected
+#[derive(Component)]
+pub struct LockedGoal;
+
+// Progress of the current attempt at a level, kept across retries so that the player can
+// respawn at the last checkpoint they reached
+#[derive(Default)]
+pub struct RunProgress {
+    // Coordinates of the last checkpoint reached: (x, y)
+    pub checkpoint: Option<(usize, usize)>,
+    // Coordinates of every nectar collected: (x, y)
+    pub nectar: HashSet<(usize, usize)>,
+    // Points earned by grazing projectiles
+    pub graze_points: u32,
+    // Time elapsed when the last checkpoint was reached
+    pub checkpoint_time: f32,
+    checkpoint_nectar: HashSet<(usize, usize)>,
+    checkpoint_graze_points: u32,
+}
+
+impl RunProgress {
+    // Reset progress back to the state it was in when the last checkpoint was reached
+    pub fn restore_checkpoint(&mut self) {
+        self.nectar = self.checkpoint_nectar.clone();
+        self.graze_points = self.checkpoint_graze_points;
+    }
+}
+
+fn reach_checkpoint(
+    mut progress: ResMut<RunProgress>,
+    game_timer: Query<&GameTimer>,
+    player: Query<(&Hitbox, &GlobalTransform), With<Player>>,
+    mut checkpoints: Query<(&Hitbox, &GlobalTransform, &Checkpoint, &mut Sprite)>,
+) {
+    if let Ok((player, player_transform)) = player.get_single() {
+        for (hitbox, transform, checkpoint, _) in checkpoints.iter() {
+            if progress.checkpoint != Some(checkpoint.coordinates)
+                && hitbox.is_collided_with(transform, player, player_transform)
+            {
+                // Take a snapshot of the run to restore when respawning
+                progress.checkpoint = Some(checkpoint.coordinates);
+                progress.checkpoint_nectar = progress.nectar.clone();
+                progress.checkpoint_graze_points = progress.graze_points;
+                progress.checkpoint_time = game_timer.single().elapsed_secs();
+            }
+        }
+    }
+
+    if progress.is_changed() {
+        // Highlight only the last checkpoint reached
+        for (_, _, checkpoint, mut sprite) in checkpoints.iter_mut() {
+            sprite.color = Checkpoint::color(progress.checkpoint == Some(checkpoint.coordinates));
+        }
+    }
+}
+
+fn collect_nectar(
+    mut commands: Commands,
+    mut progress: ResMut<RunProgress>,
+    player: Query<(&Hitbox, &GlobalTransform), With<Player>>,
+    nectar: Query<(Entity, &Hitbox, &GlobalTransform, &Nectar)>,
+) {
+    if let Ok((player, player_transform)) = player.get_single() {
+        for (entity, hitbox, transform, nectar) in nectar.iter() {
+            if hitbox.is_collided_with(transform, player, player_transform) {
+                progress.nectar.insert(nectar.coordinates);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn unlock_goals(
+    mut commands: Commands,
+    world: Res<GameWorld>,
+    progress: Res<RunProgress>,
+    mut goals: Query<(Entity, &mut TextureAtlasSprite), (With<Goal>, With<LockedGoal>)>,
+) {
+    let unlocked = progress.nectar.len() >= world.get_nectar_count();
+    for (entity, mut sprite) in goals.iter_mut() {
+        sprite.color = if unlocked {
+            commands.entity(entity).remove::<LockedGoal>();
+            Color::WHITE
+        } else {
+            // Fade out goals that are still locked
+            Color::rgba(1.0, 1.0, 1.0, 0.3)
+        };
+    }
+}
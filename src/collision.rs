@@ -1,22 +1,76 @@
-use crate::AppState;
 use bevy::prelude::*;
-use impacted::CollisionShape;
 
-pub struct CollisionPlugin;
+// Shape of an entity in its own space, which is rotated and scaled along with the entity
+#[derive(Component, Clone, Copy)]
+pub enum Hitbox {
+    Rectangle(Vec2),
+    Circle(f32),
+    // Rectangle with rounded ends lying along the y axis, the length includes the rounded ends
+    Capsule { length: f32, radius: f32 },
+}
 
-impl Plugin for CollisionPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_system_set(
-            SystemSet::on_update(AppState::Game).with_system(update_collision_transforms),
-        );
+impl Hitbox {
+    // Whether this hitbox touches the other one, exact when the other hitbox is a circle as the
+    // player's hitbox is
+    pub fn is_collided_with(
+        &self,
+        transform: &GlobalTransform,
+        other: &Hitbox,
+        other_transform: &GlobalTransform,
+    ) -> bool {
+        self.is_within(transform, other, other_transform, 0.0)
     }
-}
 
-fn update_collision_transforms(
-    mut shapes: Query<(&mut CollisionShape, &GlobalTransform), Changed<GlobalTransform>>,
-) {
-    // Iterate through all collision shapes and set transform accordingly
-    for (mut shape, transform) in shapes.iter_mut() {
-        shape.set_transform(*transform);
+    // Whether the gap between this hitbox and the other one is at most the given distance,
+    // measured the same way as is_collided_with
+    pub fn is_within(
+        &self,
+        transform: &GlobalTransform,
+        other: &Hitbox,
+        other_transform: &GlobalTransform,
+        distance: f32,
+    ) -> bool {
+        let (center, radius) = other.get_bounding_circle(other_transform);
+        self.overlaps_circle(transform, center, radius + distance)
+    }
+
+    // Smallest circle in world space that covers the hitbox: (center, radius)
+    fn get_bounding_circle(&self, transform: &GlobalTransform) -> (Vec2, f32) {
+        let scale = transform.scale.truncate();
+        let radius = match *self {
+            Hitbox::Rectangle(size) => (size * scale).length() / 2.0,
+            Hitbox::Circle(radius) => radius * scale.max_element(),
+            Hitbox::Capsule { length, radius } => (length * scale.y / 2.0).max(radius * scale.x),
+        };
+        (transform.translation.truncate(), radius)
+    }
+
+    fn overlaps_circle(&self, transform: &GlobalTransform, center: Vec2, radius: f32) -> bool {
+        // Work in the hitbox's own space, where its shape is axis aligned and centered
+        let scale = transform.scale.truncate();
+        let offset = (transform.rotation.inverse()
+            * (center.extend(transform.translation.z) - transform.translation))
+            .truncate();
+
+        match *self {
+            Hitbox::Rectangle(size) => {
+                let half_size = size * scale / 2.0;
+                let closest = offset.clamp(-half_size, half_size);
+                offset.distance(closest) <= radius
+            }
+            Hitbox::Circle(circle_radius) => {
+                offset.length() <= circle_radius * scale.max_element() + radius
+            }
+            Hitbox::Capsule {
+                length,
+                radius: capsule_radius,
+            } => {
+                // A capsule is every point within its radius of the segment through its middle
+                let capsule_radius = capsule_radius * scale.x;
+                let half_segment = (length * scale.y / 2.0 - capsule_radius).max(0.0);
+                let closest = Vec2::new(0.0, offset.y.clamp(-half_segment, half_segment));
+                offset.distance(closest) <= capsule_radius + radius
+            }
+        }
     }
 }
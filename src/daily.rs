@@ -0,0 +1,255 @@
+use crate::{
+    collectible::RunProgress,
+    generator::{self, GeneratorParams},
+    leaderboard::{LeaderboardLevel, PlayerName},
+    storage,
+    ui::{
+        spawn_back_button,
+        widgets::{
+            spawn_button_list, spawn_column, spawn_menu_screen, spawn_row, spawn_text,
+            spawn_text_input, spawn_title, TextInput,
+        },
+        GameFont,
+    },
+    upgrades::{SavedLoadout, Upgrade, UpgradeTracker},
+    util::{format_day, get_current_day},
+    world::{GameWorld, WorldType},
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const DAILY_FILE: &str = "daily.ron";
+
+// Every player plays the daily challenge with the same loadout
+const DAILY_LOADOUT: UpgradeTracker = UpgradeTracker {
+    primary: Some(Upgrade::Teleport),
+    secondary: Some(Upgrade::DoubleSpeed),
+};
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DailyAttempts>()
+            .add_system_set(SystemSet::on_enter(AppState::Daily).with_system(create_daily_menu))
+            .add_system_set(SystemSet::on_update(AppState::Daily).with_system(manage_daily_buttons))
+            .add_system_set(SystemSet::on_enter(AppState::Death).with_system(end_scored_attempt));
+    }
+}
+
+// The daily challenge is seeded with the number of days since 1970-01-01
+fn get_seed(day: i64) -> u64 {
+    day as u64
+}
+
+pub fn get_daily_world(seed: u64) -> io::Result<GameWorld> {
+    let contents = generator::generate(seed, &GeneratorParams::daily());
+    GameWorld::parse(&contents, WorldType::Daily { seed })
+}
+
+fn get_date(seed: u64) -> String {
+    format_day(seed as i64)
+}
+
+// Leaderboard of a day's challenge, which leads back to the daily challenge menu
+pub fn get_daily_leaderboard(seed: u64, highlight: Option<usize>) -> LeaderboardLevel {
+    let date = get_date(seed);
+    LeaderboardLevel {
+        level_id: format!("Daily/{date}"),
+        name: format!("Daily Challenge {date}"),
+        level_hash: get_daily_world(seed)
+            .ok()
+            .map(|world| world.get_level_hash()),
+        highlight,
+        previous_state: AppState::Daily,
+    }
+}
+
+// Attempt at the daily challenge being played
+pub struct DailyRun {
+    pub name: String,
+    // Only the first attempt of the day for each name goes on the leaderboard, the rest are
+    // practice
+    pub scored: bool,
+}
+
+// Names that have had their scored attempt at today's challenge, kept so that players can't
+// start over by restarting the game. Attempts are only told apart by name, so typing another name
+// gives another scored attempt.
+#[derive(Default, Serialize, Deserialize)]
+struct DailyAttempts {
+    day: i64,
+    names: Vec<String>,
+}
+
+impl DailyAttempts {
+    fn has_attempted(&self, day: i64, name: &str) -> bool {
+        self.day == day && self.names.iter().any(|attempted| attempted == name)
+    }
+
+    fn add(&mut self, day: i64, name: String) {
+        if self.day != day {
+            self.day = day;
+            self.names.clear();
+        }
+        self.names.push(name);
+        if let Err(error) = storage::save(DAILY_FILE, self) {
+            warn!("Failed to save daily attempts: {error}");
+        }
+    }
+}
+
+impl FromWorld for DailyAttempts {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load(DAILY_FILE)
+    }
+}
+
+// The scored attempt ends with the first death, so retries after it are practice
+fn end_scored_attempt(daily_run: Option<ResMut<DailyRun>>) {
+    if let Some(mut daily_run) = daily_run {
+        daily_run.scored = false;
+    }
+}
+
+#[derive(Component)]
+enum ButtonType {
+    Scored,
+    Practice,
+    Leaderboard,
+}
+
+#[derive(Component)]
+struct NameInput;
+
+#[derive(Component)]
+struct DailyMessage;
+
+fn create_daily_menu(mut commands: Commands, font: Res<GameFont>, player_name: Res<PlayerName>) {
+    let seed = get_seed(get_current_day());
+    let (level_hash, error) = match get_daily_world(seed) {
+        Ok(world) => (world.get_level_hash(), String::new()),
+        Err(error) => {
+            error!("Failed to load level: {error}");
+            (
+                "-".to_string(),
+                format!("Failed to load the daily level: {error}"),
+            )
+        }
+    };
+
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    spawn_back_button(&mut commands, font.get_handle());
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Daily Challenge");
+        spawn_column(parent, |parent| {
+            let details = format!(
+                "{}\nLoadout: {} / {}\nLevel hash {}",
+                get_date(seed),
+                DAILY_LOADOUT.primary.unwrap(),
+                DAILY_LOADOUT.secondary.unwrap(),
+                level_hash,
+            );
+            spawn_text(parent, &font, 35.0, [(details, Color::WHITE)]);
+            spawn_text(
+                parent,
+                &font,
+                30.0,
+                [(
+                    "Everyone gets one scored attempt a day and as much practice as they like"
+                        .to_string(),
+                    Color::GRAY,
+                )],
+            );
+            spawn_row(parent, &font, "Name", |parent| {
+                spawn_text_input(parent, &font, player_name.get_placeholder(), true)
+                    .insert(NameInput);
+            });
+            spawn_text(parent, &font, 30.0, [(error, Color::WHITE)]).insert(DailyMessage);
+            spawn_button_list(
+                parent,
+                &font,
+                [
+                    (ButtonType::Scored, "Scored Attempt"),
+                    (ButtonType::Practice, "Practice"),
+                    (ButtonType::Leaderboard, "Leaderboard"),
+                ],
+            );
+        });
+    });
+}
+
+fn manage_daily_buttons(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut attempts: ResMut<DailyAttempts>,
+    mut player_name: ResMut<PlayerName>,
+    mut upgrades: ResMut<UpgradeTracker>,
+    saved: Option<Res<SavedLoadout>>,
+    name_input: Query<&TextInput, With<NameInput>>,
+    mut message: Query<&mut Text, With<DailyMessage>>,
+    interaction: Query<(&Interaction, &ButtonType), (Changed<Interaction>, With<Button>)>,
+) {
+    let day = get_current_day();
+    let seed = get_seed(day);
+
+    for (interaction, button_type) in interaction.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        let typed = name_input
+            .get_single()
+            .map_or("", |input| input.value.as_str());
+        let name = player_name.get_entered(typed);
+
+        let scored = match button_type {
+            ButtonType::Scored if attempts.has_attempted(day, &name) => {
+                for mut text in message.iter_mut() {
+                    text.sections[0].value =
+                        format!("{name} has already had their scored attempt today");
+                }
+                continue;
+            }
+            ButtonType::Scored => true,
+            ButtonType::Practice => false,
+            ButtonType::Leaderboard => {
+                commands.insert_resource(get_daily_leaderboard(seed, None));
+                state.set(AppState::Leaderboard).unwrap();
+                continue;
+            }
+        };
+
+        let world = match get_daily_world(seed) {
+            Ok(world) => world,
+            Err(error) => {
+                error!("Failed to load level: {error}");
+                for mut text in message.iter_mut() {
+                    text.sections[0].value = format!("Failed to load the daily level: {error}");
+                }
+                continue;
+            }
+        };
+
+        // The attempt counts as soon as it starts, so that quitting doesn't give another one
+        if scored {
+            attempts.add(day, name.clone());
+        }
+        player_name.set(name.clone());
+
+        // Keep the player's own loadout to give back afterwards, unless it is already kept from
+        // an earlier attempt
+        let loadout = std::mem::replace(&mut *upgrades, DAILY_LOADOUT);
+        if saved.is_none() {
+            commands.insert_resource(SavedLoadout(loadout));
+        }
+
+        commands.insert_resource(world);
+        commands.insert_resource(RunProgress::default());
+        commands.insert_resource(DailyRun { name, scored });
+        state.set(AppState::StartDelay).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
     player::Player,
+    settings::Settings,
+    sound::{PlaySound, SoundEffect},
     util::{AnimatedSprite, AnimatedSpriteData},
     AppState,
 };
@@ -41,12 +43,15 @@ fn spawn_death_anim(
     player_info: Query<(Entity, &Transform), With<Player>>,
     asset_server: Res<AssetServer>,
     mut textures: ResMut<Assets<TextureAtlas>>,
+    mut sounds: EventWriter<PlaySound>,
 ) {
     commands.spawn_bundle((Timer::from_seconds(3.0, false), DeathTimer));
 
     let (player_entity, player_transform) = player_info.single();
     let player_transform = *player_transform;
 
+    sounds.send(PlaySound::new(SoundEffect::Explosion));
+
     commands.entity(player_entity).despawn_recursive();
 
     commands
@@ -74,6 +79,7 @@ fn end_death_anim(
     mut animations: ResMut<Assets<SpriteSheetAnimation>>,
     mut textures: ResMut<Assets<TextureAtlas>>,
     asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
     query: Query<(Entity, &Transform, Option<&Play>), With<Player>>,
 ) {
     if let Ok((player_entity, player_transform, playing)) = query.get_single() {
@@ -82,6 +88,9 @@ fn end_death_anim(
             // the animation is no longer playing (AnimationMode::Once)
             // destroy the player death entity
             commands.entity(player_entity).despawn_recursive();
+            if settings.reduced_motion {
+                return;
+            }
             // create death flakes
             const DEATH_SHARDS: usize = 6;
             for i in 0..DEATH_SHARDS {
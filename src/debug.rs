@@ -0,0 +1,278 @@
+use crate::{
+    accessibility::spawn_hitbox_outline,
+    assist::AssistSettings,
+    collision::Hitbox,
+    enemy::Projectile,
+    player::InvincibilityTimer,
+    ui::GameFont,
+    world::{GameWorld, Spawner, Tile},
+    AppState,
+};
+use bevy::{
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+const OUTLINE_COLOR: Color = Color::YELLOW;
+const GRID_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.3);
+const LABEL_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+const LABEL_FONT_SIZE: f32 = 7.0;
+// Above every tile, projectile and the player
+const DEBUG_Z: f32 = 5.0;
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(FrameTimeDiagnosticsPlugin::default())
+            .init_resource::<ShowDebugOverlay>()
+            .add_system(toggle_debug_overlay)
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(outline_hitboxes)
+                    .with_system(spawn_grid)
+                    .with_system(label_spawners)
+                    .with_system(update_spawner_labels)
+                    .with_system(update_debug_text),
+            );
+    }
+}
+
+// Toggled with F3
+#[derive(Default)]
+struct ShowDebugOverlay {
+    visible: bool,
+    // Counts how many times the overlay has been shown, so that entities marked while it was
+    // shown before are outlined and labeled again
+    generation: u32,
+}
+
+// Everything spawned by the debug overlay, despawned when it is hidden
+#[derive(Component, Clone, Copy)]
+struct DebugEntity;
+
+// Entities that have been given a debug outline or label, in the given generation
+#[derive(Component)]
+struct DebugMarked(u32);
+
+fn is_marked(show: &ShowDebugOverlay, marked: Option<&DebugMarked>) -> bool {
+    marked.map_or(false, |marked| marked.0 == show.generation)
+}
+
+#[derive(Component)]
+struct DebugGrid;
+
+// Text showing the time until a spawner fires next
+#[derive(Component)]
+struct SpawnerLabel(Entity);
+
+#[derive(Component)]
+struct DebugText;
+
+fn toggle_debug_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut show: ResMut<ShowDebugOverlay>,
+    debug_entities: Query<Entity, With<DebugEntity>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+    show.visible = !show.visible;
+
+    if show.visible {
+        show.generation += 1;
+    } else {
+        for entity in debug_entities.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn outline_hitboxes(
+    mut commands: Commands,
+    show: Res<ShowDebugOverlay>,
+    asset_server: Res<AssetServer>,
+    hitboxes: Query<(Entity, &Hitbox, Option<&DebugMarked>)>,
+) {
+    if show.visible {
+        for (entity, hitbox, marked) in hitboxes.iter() {
+            if is_marked(&show, marked) {
+                continue;
+            }
+            commands
+                .entity(entity)
+                .insert(DebugMarked(show.generation))
+                .with_children(|parent| {
+                    spawn_hitbox_outline(parent, &asset_server, *hitbox, OUTLINE_COLOR, DebugEntity)
+                });
+        }
+    }
+}
+
+fn label_text(value: String, font: &GameFont) -> Text {
+    Text::with_section(
+        value,
+        TextStyle {
+            font: font.get_handle(),
+            font_size: LABEL_FONT_SIZE,
+            color: LABEL_COLOR,
+        },
+        TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Center,
+        },
+    )
+}
+
+// Lines between tiles with the coordinates of every tile, as used in level files: (x, y)
+fn spawn_grid(
+    mut commands: Commands,
+    show: Res<ShowDebugOverlay>,
+    font: Res<GameFont>,
+    world: Res<GameWorld>,
+    grid: Query<(), With<DebugGrid>>,
+) {
+    if !show.visible || grid.iter().next().is_some() {
+        return;
+    }
+
+    let (min, max) = world.get_bounds();
+    let size = max - min;
+    let mut spawn_line = |position: Vec2, size: Vec2| {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: GRID_COLOR,
+                    custom_size: Some(size),
+                    ..Sprite::default()
+                },
+                transform: Transform::from_translation(position.extend(DEBUG_Z)),
+                ..SpriteBundle::default()
+            })
+            .insert(DebugGrid)
+            .insert(DebugEntity);
+    };
+    let columns = (size.x / Tile::SIZE).round() as usize;
+    let rows = (size.y / Tile::SIZE).round() as usize;
+    for column in 0..=columns {
+        let x = min.x + column as f32 * Tile::SIZE;
+        spawn_line(Vec2::new(x, (min.y + max.y) / 2.0), Vec2::new(1.0, size.y));
+    }
+    for row in 0..=rows {
+        let y = min.y + row as f32 * Tile::SIZE;
+        spawn_line(Vec2::new((min.x + max.x) / 2.0, y), Vec2::new(size.x, 1.0));
+    }
+
+    for (y, row) in world.layout.iter().enumerate() {
+        for x in 0..row.len() {
+            let position = Vec2::new(x as f32, -(y as f32)) * Tile::SIZE;
+            commands
+                .spawn_bundle(Text2dBundle {
+                    text: label_text(format!("{x},{y}"), &font),
+                    transform: Transform::from_translation(position.extend(DEBUG_Z)),
+                    ..Text2dBundle::default()
+                })
+                .insert(DebugGrid)
+                .insert(DebugEntity);
+        }
+    }
+}
+
+fn label_spawners(
+    mut commands: Commands,
+    show: Res<ShowDebugOverlay>,
+    font: Res<GameFont>,
+    spawners: Query<(Entity, &Transform, Option<&DebugMarked>), With<Spawner>>,
+) {
+    if show.visible {
+        for (entity, transform, marked) in spawners.iter() {
+            if is_marked(&show, marked) {
+                continue;
+            }
+            // Spawned separately rather than as a child so that the text is not rotated with
+            // laser spawners
+            let position = transform.translation.truncate() + Vec2::new(0.0, Tile::SIZE / 3.0);
+            commands
+                .spawn_bundle(Text2dBundle {
+                    text: label_text(String::new(), &font),
+                    transform: Transform::from_translation(position.extend(DEBUG_Z + 0.1)),
+                    ..Text2dBundle::default()
+                })
+                .insert(SpawnerLabel(entity))
+                .insert(DebugEntity);
+            commands.entity(entity).insert(DebugMarked(show.generation));
+        }
+    }
+}
+
+fn update_spawner_labels(spawners: Query<&Spawner>, mut labels: Query<(&SpawnerLabel, &mut Text)>) {
+    for (label, mut text) in labels.iter_mut() {
+        if let Ok(spawner) = spawners.get(label.0) {
+            text.sections[0].value = format!("{:.2}", spawner.get_remaining_secs());
+        }
+    }
+}
+
+fn update_debug_text(
+    mut commands: Commands,
+    show: Res<ShowDebugOverlay>,
+    font: Res<GameFont>,
+    diagnostics: Res<Diagnostics>,
+    invincibility_timer: Res<InvincibilityTimer>,
+    assists: Res<AssistSettings>,
+    projectiles: Query<&Projectile>,
+    mut text: Query<&mut Text, With<DebugText>>,
+) {
+    if !show.visible {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.average())
+        .unwrap_or(0.0);
+    let missiles = projectiles
+        .iter()
+        .filter(|projectile| matches!(projectile, Projectile::Missile))
+        .count();
+    let lasers = projectiles.iter().count() - missiles;
+    let invincibility = if assists.invincible {
+        "Invincible (assist)".to_string()
+    } else if invincibility_timer.get_remaining_secs() > 0.0 {
+        format!("Invincible {:.2}", invincibility_timer.get_remaining_secs())
+    } else {
+        "Vulnerable".to_string()
+    };
+    let value = format!("FPS {fps:.0}\nMissiles {missiles}\nLasers {lasers}\n{invincibility}");
+
+    match text.get_single_mut() {
+        Ok(mut text) => text.sections[0].value = value,
+        Err(_) => {
+            commands
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: Rect {
+                            bottom: Val::Percent(2.0),
+                            left: Val::Percent(2.0),
+                            ..Rect::default()
+                        },
+                        ..Style::default()
+                    },
+                    text: Text::with_section(
+                        value,
+                        TextStyle {
+                            font: font.get_handle(),
+                            font_size: 25.0,
+                            color: Color::YELLOW,
+                        },
+                        TextAlignment::default(),
+                    ),
+                    ..TextBundle::default()
+                })
+                .insert(DebugText)
+                .insert(DebugEntity);
+        }
+    }
+}
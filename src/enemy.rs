@@ -1,4 +1,7 @@
 use crate::{
+    accessibility::Hazard,
+    assist::GameTime,
+    collision::Hitbox,
     player::Player,
     pursue::pursue,
     upgrades::{Upgrade, UpgradeTracker},
@@ -9,7 +12,6 @@ use crate::{
 };
 use benimator::SpriteSheetAnimation;
 use bevy::prelude::*;
-use impacted::CollisionShape;
 use std::f32::consts::PI;
 
 const LASER_SCALE_INTERPOLATION: f32 = 0.08;
@@ -65,10 +67,17 @@ pub enum Projectile {
 
 impl Projectile {
     const MISSILE_SIZE: (f32, f32) = (24.0, 24.0);
+    // Covers the body of the rocket but not the tips of its fins
+    const MISSILE_HITBOX: Hitbox = Hitbox::Rectangle(Vec2::new(8.0, 20.0));
     const MISSILE_VELOCITY: f32 = 300.0;
     pub const MISSILE_COOLDOWN: f32 = 1.0;
 
     const LASER_SIZE: (f32, f32) = (12.0, 24.0);
+    // Stays inside the faded edges of the laser
+    const LASER_HITBOX: Hitbox = Hitbox::Capsule {
+        length: 20.0,
+        radius: 4.5,
+    };
     const LASER_VELOCITY: f32 = 225.0;
     pub const LASER_COOLDOWN: f32 = 0.1;
 
@@ -97,11 +106,9 @@ impl Projectile {
                             ..AnimatedSpriteData::default()
                         },
                     ))
-                    .insert(CollisionShape::new_rectangle(
-                        Self::MISSILE_SIZE.0,
-                        Self::MISSILE_SIZE.1,
-                    ))
+                    .insert(Self::MISSILE_HITBOX)
                     .insert(Pursuer::new(Self::MISSILE_VELOCITY))
+                    .insert(Hazard::Missile)
                     .insert(self.clone())
                     .insert(Enemy);
             }
@@ -124,11 +131,9 @@ impl Projectile {
                             ..AnimatedSpriteData::default()
                         },
                     ))
-                    .insert(CollisionShape::new_rectangle(
-                        Self::LASER_SIZE.0,
-                        Self::LASER_SIZE.1,
-                    ))
+                    .insert(Self::LASER_HITBOX)
                     .insert(Bullet::new(Self::LASER_VELOCITY, *angle))
+                    .insert(Hazard::Laser)
                     .insert(self.clone())
                     .insert(Enemy);
             }
@@ -137,7 +142,7 @@ impl Projectile {
 }
 
 fn follow_player(
-    time: Res<Time>,
+    time: Res<GameTime>,
     player_transform: Query<&Transform, (With<Player>, Without<Projectile>)>,
     mut enemies: Query<(&mut Transform, &Pursuer), With<Projectile>>,
     upgrades: Res<UpgradeTracker>,
@@ -163,7 +168,7 @@ fn follow_player(
 }
 
 fn move_bullet_enemies(
-    time: Res<Time>,
+    time: Res<GameTime>,
     mut enemies: Query<(&mut Transform, &Bullet), With<Projectile>>,
     upgrades: Res<UpgradeTracker>,
 ) {
@@ -177,7 +182,9 @@ fn move_bullet_enemies(
                 1.0
             })
         .extend(0.0);
-        transform.scale = transform.scale.lerp(Vec3::ONE, LASER_SCALE_INTERPOLATION);
+        // Interpolation is tuned per frame at 60 fps, adjust it to the game speed
+        let interpolation = LASER_SCALE_INTERPOLATION * time.delta_seconds() * 60.0;
+        transform.scale = transform.scale.lerp(Vec3::ONE, interpolation.min(1.0));
     }
 }
 
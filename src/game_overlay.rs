@@ -1,6 +1,16 @@
-use bevy::utils::Instant;
+use bevy::utils::Duration;
 
-use crate::{ui::GameFont, upgrades::create_upgrades_overlay, AppState};
+use crate::{
+    assist::{AssistSettings, GameTime},
+    collectible::RunProgress,
+    minimap::{create_minimap, ShowMinimap},
+    score::{get_score, GrazeCombo},
+    settings::Settings,
+    ui::{GameFont, ACTIVE_BUTTON_COLOR},
+    upgrades::create_upgrades_overlay,
+    world::GameWorld,
+    AppState,
+};
 use bevy::prelude::*;
 
 pub struct GameOverlayPlugin;
@@ -8,22 +18,77 @@ pub struct GameOverlayPlugin;
 impl Plugin for GameOverlayPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(create_game_overlay))
-            .add_system_set(SystemSet::on_update(AppState::Game).with_system(update_game_timer));
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(tick_game_timer)
+                    .with_system(update_game_timer)
+                    .with_system(update_nectar_counter)
+                    .with_system(update_score),
+            );
     }
 }
 
+// Time spent in the level, which runs at the game speed
 #[derive(Component)]
-struct GameTimer(Instant);
+pub struct GameTimer(Duration);
 
-impl Default for GameTimer {
-    fn default() -> Self {
-        Self(Instant::now())
+impl GameTimer {
+    // Create a timer that has already been running for the given number of seconds
+    fn with_elapsed_secs(secs: f32) -> Self {
+        Self(Duration::from_secs_f32(secs))
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.0.as_secs_f32()
+    }
+}
+
+fn tick_game_timer(time: Res<GameTime>, mut game_timer: Query<&mut GameTimer>) {
+    for mut game_timer in game_timer.iter_mut() {
+        game_timer.0 += time.delta();
     }
 }
 
-fn create_game_overlay(mut commands: Commands, font: Res<GameFont>) {
+#[derive(Component)]
+struct NectarCounter;
+
+#[derive(Component)]
+struct ScoreText;
+
+fn create_game_overlay(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    world: Res<GameWorld>,
+    progress: Res<RunProgress>,
+    settings: Res<Settings>,
+    assists: Res<AssistSettings>,
+    show_minimap: Res<ShowMinimap>,
+    mut images: ResMut<Assets<Image>>,
+) {
     commands.spawn_bundle(UiCameraBundle::default());
     create_upgrades_overlay(&mut commands, &font);
+    create_minimap(&mut commands, &mut images, &world, &show_minimap);
+
+    let mut timer_text = Text::with_section(
+        "",
+        TextStyle {
+            font: font.get_handle(),
+            font_size: 50.0,
+            ..TextStyle::default()
+        },
+        TextAlignment::default(),
+    );
+    // Flag runs that are recorded separately because they use assists
+    if assists.is_active() {
+        timer_text.sections.push(TextSection {
+            value: "  Assist".into(),
+            style: TextStyle {
+                font: font.get_handle(),
+                font_size: 30.0,
+                color: ACTIVE_BUTTON_COLOR,
+            },
+        });
+    }
 
     commands
         .spawn_bundle(TextBundle {
@@ -36,22 +101,102 @@ fn create_game_overlay(mut commands: Commands, font: Res<GameFont>) {
                 },
                 ..Style::default()
             },
-            text: Text::with_section(
-                "",
-                TextStyle {
-                    font: font.get_handle(),
-                    font_size: 50.0,
-                    ..TextStyle::default()
+            text: timer_text,
+            visibility: Visibility {
+                is_visible: settings.show_timer,
+            },
+            ..TextBundle::default()
+        })
+        // Continue timing from the last checkpoint reached
+        .insert(GameTimer::with_elapsed_secs(progress.checkpoint_time));
+
+    // Score with the graze combo multiplier next to it
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Percent(10.0),
+                    left: Val::Percent(2.0),
+                    ..Rect::default()
                 },
-                TextAlignment::default(),
-            ),
+                ..Style::default()
+            },
+            text: Text {
+                sections: [Color::WHITE, ACTIVE_BUTTON_COLOR]
+                    .into_iter()
+                    .map(|color| TextSection {
+                        value: String::new(),
+                        style: TextStyle {
+                            font: font.get_handle(),
+                            font_size: 35.0,
+                            color,
+                        },
+                    })
+                    .collect(),
+                ..Text::default()
+            },
             ..TextBundle::default()
         })
-        .insert(GameTimer::default());
+        .insert(ScoreText);
+
+    // Only show the nectar counter in levels that contain nectar
+    if world.get_nectar_count() > 0 {
+        commands
+            .spawn_bundle(TextBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        top: Val::Percent(2.0),
+                        right: Val::Percent(2.0),
+                        ..Rect::default()
+                    },
+                    ..Style::default()
+                },
+                text: Text::with_section(
+                    "",
+                    TextStyle {
+                        font: font.get_handle(),
+                        font_size: 50.0,
+                        ..TextStyle::default()
+                    },
+                    TextAlignment::default(),
+                ),
+                ..TextBundle::default()
+            })
+            .insert(NectarCounter);
+    }
 }
 
 fn update_game_timer(mut text: Query<(&mut Text, &GameTimer)>) {
     let (mut text, timer) = text.single_mut();
-    let time = (Instant::now() - timer.0).as_secs_f32();
+    let time = timer.elapsed_secs();
     text.sections[0].value = format!("{time:.2}");
 }
+
+fn update_nectar_counter(
+    world: Res<GameWorld>,
+    progress: Res<RunProgress>,
+    mut text: Query<&mut Text, With<NectarCounter>>,
+) {
+    if let Ok(mut text) = text.get_single_mut() {
+        let (collected, total) = (progress.nectar.len(), world.get_nectar_count());
+        text.sections[0].value = format!("Nectar {collected}/{total}");
+    }
+}
+
+fn update_score(
+    progress: Res<RunProgress>,
+    combo: Res<GrazeCombo>,
+    game_timer: Query<&GameTimer>,
+    mut text: Query<&mut Text, With<ScoreText>>,
+) {
+    let mut text = text.single_mut();
+    let score = get_score(game_timer.single().elapsed_secs(), &progress);
+    text.sections[0].value = format!("Score {score}");
+    text.sections[1].value = if combo.multiplier > 1.0 {
+        format!("  x{:.1}", combo.multiplier)
+    } else {
+        String::new()
+    };
+}
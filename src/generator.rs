@@ -0,0 +1,431 @@
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{collections::VecDeque, fs, io, path::Path};
+
+// Offsets to the neighbors of a cell or tile: (x, y)
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+// Angle that a laser in a level file fires at for each direction, in radians. Rows go down in
+// level files but up in the world, so up is a quarter turn.
+const LASER_ANGLES: [&str; 4] = ["1.57", "0", "4.71", "3.14"];
+
+// Settings for generated levels, which can be set from the command line as option=value
+pub struct GeneratorParams {
+    // Size of the level in tiles including the outer walls, rounded down to fit whole corridors
+    pub width: usize,
+    pub height: usize,
+    // Width of the corridors in tiles, the walls between them are always one tile thick
+    pub corridor_width: usize,
+    // Chance for each corner of the maze to get a spawner, between 0 and 1
+    pub spawner_density: f64,
+    // Only put spawners in dead ends, out of the way of the path to the goal
+    pub dead_end_spawners: bool,
+    // Share of spawners that fire lasers rather than missiles, between 0 and 1
+    pub laser_ratio: f64,
+    // Traps that would cut off the goal are left out, so there may be fewer of them
+    pub trap_count: usize,
+}
+
+impl Default for GeneratorParams {
+    fn default() -> Self {
+        Self {
+            width: 31,
+            height: 19,
+            corridor_width: 2,
+            spawner_density: 0.25,
+            dead_end_spawners: false,
+            laser_ratio: 0.3,
+            trap_count: 6,
+        }
+    }
+}
+
+impl GeneratorParams {
+    // Settings of the daily challenge, which must stay the same so that the level of every past
+    // day stays the same too
+    pub fn daily() -> Self {
+        Self {
+            spawner_density: 0.5,
+            dead_end_spawners: true,
+            laser_ratio: 0.0,
+            trap_count: 0,
+            ..Self::default()
+        }
+    }
+
+    // Parse options given as option=value, such as width=40
+    pub fn parse(options: &[String]) -> io::Result<Self> {
+        let mut params = Self::default();
+        for option in options {
+            let invalid_option = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid option: {option}"),
+                )
+            };
+            let (name, value) = option.split_once('=').ok_or_else(invalid_option)?;
+            let parsed = match name {
+                "width" => value.parse().map(|value| params.width = value).is_ok(),
+                "height" => value.parse().map(|value| params.height = value).is_ok(),
+                "corridor_width" => value
+                    .parse()
+                    .map(|value| params.corridor_width = value)
+                    .is_ok(),
+                "spawner_density" => value
+                    .parse()
+                    .map(|value| params.spawner_density = value)
+                    .is_ok(),
+                "dead_end_spawners" => value
+                    .parse()
+                    .map(|value| params.dead_end_spawners = value)
+                    .is_ok(),
+                "laser_ratio" => value
+                    .parse()
+                    .map(|value| params.laser_ratio = value)
+                    .is_ok(),
+                "trap_count" => value.parse().map(|value| params.trap_count = value).is_ok(),
+                _ => false,
+            };
+            if !parsed {
+                return Err(invalid_option());
+            }
+        }
+        Ok(params)
+    }
+
+    // Number of maze cells that fit across and down the level. There are at least two so that the
+    // start and the goal are in different cells.
+    fn get_maze_size(&self) -> (usize, usize) {
+        let cell_size = self.get_corridor_width() + 1;
+        let width = (self.width.saturating_sub(1) / cell_size).max(1);
+        let height = (self.height.saturating_sub(1) / cell_size).max(1);
+        if width * height < 2 {
+            (2, 1)
+        } else {
+            (width, height)
+        }
+    }
+
+    fn get_corridor_width(&self) -> usize {
+        self.corridor_width.max(1)
+    }
+}
+
+// Maze whose cells are connected by the passages carved between them, so there is exactly one
+// way between any two cells
+struct Maze {
+    width: usize,
+    height: usize,
+    // Whether the passage from each cell in each of the directions is open
+    passages: Vec<[bool; 4]>,
+}
+
+impl Maze {
+    // Carve passages with a randomized depth first search, which reaches every cell
+    fn carve(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Self {
+        let mut passages = vec![[false; 4]; width * height];
+        let mut visited = vec![false; width * height];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+
+        while let Some(&(x, y)) = stack.last() {
+            let mut directions: Vec<usize> = (0..4)
+                .filter(|direction| {
+                    step(width, height, (x, y), *direction)
+                        .map_or(false, |(nx, ny)| !visited[ny * width + nx])
+                })
+                .collect();
+            directions.shuffle(rng);
+
+            match directions.first() {
+                Some(&direction) => {
+                    let (nx, ny) = step(width, height, (x, y), direction).unwrap();
+                    passages[y * width + x][direction] = true;
+                    passages[ny * width + nx][(direction + 2) % 4] = true;
+                    visited[ny * width + nx] = true;
+                    stack.push((nx, ny));
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            passages,
+        }
+    }
+
+    // Number of passages between each cell and the given one
+    fn get_distances(&self, from: (usize, usize)) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.width * self.height];
+        distances[from.1 * self.width + from.0] = 0;
+        let mut queue = VecDeque::from([from]);
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[y * self.width + x];
+            for direction in 0..4 {
+                if !self.passages[y * self.width + x][direction] {
+                    continue;
+                }
+                let (nx, ny) = step(self.width, self.height, (x, y), direction).unwrap();
+                if distances[ny * self.width + nx] == usize::MAX {
+                    distances[ny * self.width + nx] = distance + 1;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        distances
+    }
+
+    fn is_dead_end(&self, (x, y): (usize, usize)) -> bool {
+        self.passages[y * self.width + x]
+            .iter()
+            .filter(|open| **open)
+            .count()
+            == 1
+    }
+}
+
+// Neighbor of a position in a grid of the given size, if it is inside the grid
+fn step(
+    width: usize,
+    height: usize,
+    (x, y): (usize, usize),
+    direction: usize,
+) -> Option<(usize, usize)> {
+    let (dx, dy) = DIRECTIONS[direction];
+    let (nx, ny) = (x as isize + dx, y as isize + dy);
+    (nx >= 0 && ny >= 0 && (nx as usize) < width && (ny as usize) < height)
+        .then(|| (nx as usize, ny as usize))
+}
+
+// Whether the bee can get from one tile to the other without touching walls or traps
+fn has_path(tiles: &[Vec<String>], from: (usize, usize), to: (usize, usize)) -> bool {
+    let (width, height) = (tiles[0].len(), tiles.len());
+    let mut visited = vec![vec![false; width]; height];
+    visited[from.1][from.0] = true;
+    let mut queue = VecDeque::from([from]);
+    while let Some(position) = queue.pop_front() {
+        if position == to {
+            return true;
+        }
+        for direction in 0..4 {
+            if let Some((nx, ny)) = step(width, height, position, direction) {
+                if !visited[ny][nx] && tiles[ny][nx] != "#" && tiles[ny][nx] != "T" {
+                    visited[ny][nx] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+    }
+    false
+}
+
+// Generate a level in the same format as level files, the same seed and parameters always give
+// the same level on every platform and version, unlike StdRng
+pub fn generate(seed: u64, params: &GeneratorParams) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let (maze_width, maze_height) = params.get_maze_size();
+    let maze = Maze::carve(maze_width, maze_height, &mut rng);
+
+    // Cells are as wide as a corridor with a wall between neighboring cells
+    let corridor_width = params.get_corridor_width();
+    let cell_size = corridor_width + 1;
+    let mut tiles =
+        vec![vec!["#".to_string(); maze_width * cell_size + 1]; maze_height * cell_size + 1];
+    let cell_origin = |(x, y): (usize, usize)| (x * cell_size + 1, y * cell_size + 1);
+    for y in 0..maze_height {
+        for x in 0..maze_width {
+            let (left, top) = cell_origin((x, y));
+            for row in &mut tiles[top..top + corridor_width] {
+                for tile in &mut row[left..left + corridor_width] {
+                    *tile = ".".into();
+                }
+            }
+            // Passages only need to be opened towards the right and the bottom, the other ones
+            // are opened from the neighboring cell
+            let passages = maze.passages[y * maze_width + x];
+            if passages[1] {
+                for row in &mut tiles[top..top + corridor_width] {
+                    row[left + corridor_width] = ".".into();
+                }
+            }
+            if passages[2] {
+                for tile in &mut tiles[top + corridor_width][left..left + corridor_width] {
+                    *tile = ".".into();
+                }
+            }
+        }
+    }
+
+    // Start in the top left and put the goal in the cell furthest from it
+    let start_cell = (0, 0);
+    let distances = maze.get_distances(start_cell);
+    let goal_cell = (0..maze_width * maze_height)
+        .max_by_key(|cell| distances[*cell])
+        .map(|cell| (cell % maze_width, cell / maze_width))
+        .unwrap();
+    let start = cell_origin(start_cell);
+    let goal = cell_origin(goal_cell);
+    tiles[start.1][start.0] = "*".into();
+    tiles[goal.1][goal.0] = "G".into();
+
+    // Spawners sit in the corner of their cell and don't block the way. Missiles chase the bee
+    // from anywhere, while lasers fire down one of the passages out of their cell
+    for y in 0..maze_height {
+        for x in 0..maze_width {
+            let cell = (x, y);
+            if cell == start_cell
+                || cell == goal_cell
+                || (params.dead_end_spawners && !maze.is_dead_end(cell))
+                || !rng.gen_bool(params.spawner_density.clamp(0.0, 1.0))
+            {
+                continue;
+            }
+            let (left, top) = cell_origin(cell);
+            let is_laser =
+                params.laser_ratio > 0.0 && rng.gen_bool(params.laser_ratio.clamp(0.0, 1.0));
+            tiles[top][left] = if is_laser {
+                let open: Vec<usize> = (0..4)
+                    .filter(|direction| maze.passages[y * maze_width + x][*direction])
+                    .collect();
+                // Every cell of a maze with more than one cell has a passage out of it
+                let direction = open.choose(&mut rng).unwrap();
+                format!("L:{}", LASER_ANGLES[*direction])
+            } else {
+                "M".into()
+            };
+        }
+    }
+
+    // Traps go on open tiles away from the start, skipping any that would cut off the goal
+    let mut open_tiles: Vec<(usize, usize)> = (0..tiles.len())
+        .flat_map(|y| (0..tiles[y].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            tiles[y][x] == "." && (x >= start.0 + cell_size || y >= start.1 + cell_size)
+        })
+        .collect();
+    open_tiles.shuffle(&mut rng);
+    let mut traps = 0;
+    for (x, y) in open_tiles {
+        if traps == params.trap_count {
+            break;
+        }
+        tiles[y][x] = "T".into();
+        if has_path(&tiles, start, goal) {
+            traps += 1;
+        } else {
+            tiles[y][x] = ".".into();
+        }
+    }
+
+    tiles
+        .iter()
+        .map(|row| row.join("\t"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Write a generated level to a file that can be opened in a spreadsheet or put in a level pack
+pub fn export_level(path: &Path, seed: u64, params: &GeneratorParams) -> io::Result<()> {
+    fs::write(path, generate(seed, params) + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_tiles(level: &str) -> Vec<Vec<String>> {
+        level
+            .lines()
+            .map(|line| line.split('\t').map(String::from).collect())
+            .collect()
+    }
+
+    fn find(tiles: &[Vec<String>], value: &str) -> Vec<(usize, usize)> {
+        (0..tiles.len())
+            .flat_map(|y| (0..tiles[y].len()).map(move |x| (x, y)))
+            .filter(|&(x, y)| tiles[y][x] == value)
+            .collect()
+    }
+
+    fn options(options: &[&str]) -> Vec<String> {
+        options.iter().map(|option| option.to_string()).collect()
+    }
+
+    #[test]
+    fn generate_is_deterministic() {
+        let params = GeneratorParams::default();
+        for seed in 0..20 {
+            assert_eq!(generate(seed, &params), generate(seed, &params));
+        }
+        assert_ne!(generate(1, &params), generate(2, &params));
+    }
+
+    // Everyone has to get the same daily level, so its layout must never change
+    #[test]
+    fn daily_level_is_stable() {
+        let expected = [
+            "###############################",
+            "#*....#..............#M.....G.#",
+            "#.....#..............#........#",
+            "####..#..#..#######..####..####",
+            "#.....#..#.....#M.......#.....#",
+            "#.....#..#.....#........#.....#",
+            "#..####..####..#######..####..#",
+            "#.....#..#..#.....#M.#.....#..#",
+            "#.....#..#..#.....#..#.....#..#",
+            "####..#..#..####..#..####..#..#",
+            "#.....#......M.#..#..#.....#..#",
+            "#.....#........#..#..#.....#..#",
+            "#..#############..#..#..####..#",
+            "#..#.....#........#.....#M....#",
+            "#..#.....#........#.....#.....#",
+            "#..#..#..#..#######..#######..#",
+            "#.....#.....#M................#",
+            "#.....#.....#.................#",
+            "###############################",
+        ];
+        let level = generate(19000, &GeneratorParams::daily()).replace('\t', "");
+        assert_eq!(level.lines().collect::<Vec<&str>>(), expected);
+    }
+
+    #[test]
+    fn goal_can_be_reached_from_start() {
+        let param_sets = [
+            GeneratorParams::default(),
+            GeneratorParams::daily(),
+            GeneratorParams::parse(&options(&["corridor_width=1", "trap_count=500"])).unwrap(),
+            GeneratorParams::parse(&options(&["width=3", "corridor_width=2"])).unwrap(),
+            GeneratorParams::parse(&options(&["width=0", "height=0"])).unwrap(),
+        ];
+        for params in &param_sets {
+            for seed in 0..50 {
+                let tiles = to_tiles(&generate(seed, params));
+                let starts = find(&tiles, "*");
+                let goals = find(&tiles, "G");
+                assert_eq!(starts.len(), 1);
+                assert_eq!(goals.len(), 1);
+                assert!(has_path(&tiles, starts[0], goals[0]));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_reads_options() {
+        let params = GeneratorParams::parse(&options(&["width=40", "laser_ratio=0.5"])).unwrap();
+        assert_eq!(params.width, 40);
+        assert_eq!(params.laser_ratio, 0.5);
+        assert_eq!(params.height, GeneratorParams::default().height);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_options() {
+        for option in ["width", "width=wide", "trap_count=-1", "size=10", "=3"] {
+            let error = GeneratorParams::parse(&options(&[option])).err().unwrap();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            assert!(error.to_string().contains(option));
+        }
+    }
+}
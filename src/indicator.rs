@@ -0,0 +1,261 @@
+use crate::{
+    camera::MainCamera,
+    enemy::Projectile,
+    player::Player,
+    ui::{GameFont, ACTIVE_BUTTON_COLOR},
+    world::{Goal, Tile},
+    AppState,
+};
+use bevy::prelude::*;
+
+// Size of an arrow on screen in pixels
+const ARROW_SIZE: f32 = 24.0;
+
+// Distance between the arrows and the edge of the screen in pixels
+const EDGE_MARGIN: f32 = 30.0;
+
+// Missiles further than this from the edge of the view are not considered incoming
+const MISSILE_WARNING_DISTANCE: f32 = 400.0;
+
+pub struct IndicatorPlugin;
+
+impl Plugin for IndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(create_goal_indicator))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(update_goal_indicator)
+                    .with_system(update_missile_indicators),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Game).with_system(hide_indicators));
+    }
+}
+
+#[derive(Component)]
+struct GoalIndicator;
+
+#[derive(Component)]
+struct GoalDistance;
+
+#[derive(Component)]
+struct MissileIndicator;
+
+// Visible area of the main camera in world coordinates
+struct View {
+    center: Vec2,
+    half_size: Vec2,
+    // World units per screen pixel
+    scale: f32,
+}
+
+impl View {
+    fn new(transform: &Transform, projection: &OrthographicProjection) -> Self {
+        Self {
+            center: transform.translation.truncate(),
+            half_size: Vec2::new(projection.right, projection.top) * projection.scale,
+            scale: projection.scale,
+        }
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        let offset = (position - self.center).abs();
+        offset.x <= self.half_size.x && offset.y <= self.half_size.y
+    }
+
+    // Point on the edge of the view in the direction of the given position
+    fn get_edge_point(&self, position: Vec2) -> Vec2 {
+        let direction = position - self.center;
+        let bounds = self.half_size - Vec2::splat(EDGE_MARGIN * self.scale);
+        let fit = (bounds / direction.abs()).min_element();
+        self.center + direction * fit
+    }
+
+    // Transform that places an arrow on the edge of the view, pointing at the given position
+    fn get_arrow_transform(&self, position: Vec2) -> Transform {
+        let direction = position - self.center;
+        Transform {
+            // Draw arrows above everything else in the world
+            translation: self.get_edge_point(position).extend(10.0),
+            rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+            // Keep the same size on screen regardless of camera zoom
+            scale: Vec3::splat(self.scale),
+        }
+    }
+}
+
+fn arrow_bundle(asset_server: &AssetServer, color: Color) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            color,
+            custom_size: Some(Vec2::splat(ARROW_SIZE)),
+            ..Sprite::default()
+        },
+        texture: asset_server.load("arrow.png"),
+        visibility: Visibility { is_visible: false },
+        ..SpriteBundle::default()
+    }
+}
+
+fn create_goal_indicator(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    font: Res<GameFont>,
+) {
+    commands
+        .spawn_bundle(arrow_bundle(&asset_server, ACTIVE_BUTTON_COLOR))
+        .insert(GoalIndicator);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: font.get_handle(),
+                    font_size: 20.0,
+                    color: ACTIVE_BUTTON_COLOR,
+                },
+                TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center,
+                },
+            ),
+            visibility: Visibility { is_visible: false },
+            ..Text2dBundle::default()
+        })
+        .insert(GoalDistance);
+}
+
+fn update_goal_indicator(
+    camera: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    player: Query<&Transform, With<Player>>,
+    goals: Query<&Transform, With<Goal>>,
+    mut arrow: Query<
+        (&mut Transform, &mut Visibility),
+        (
+            With<GoalIndicator>,
+            Without<MainCamera>,
+            Without<Player>,
+            Without<Goal>,
+        ),
+    >,
+    mut distance: Query<
+        (&mut Transform, &mut Visibility, &mut Text),
+        (
+            With<GoalDistance>,
+            Without<GoalIndicator>,
+            Without<MainCamera>,
+            Without<Player>,
+            Without<Goal>,
+        ),
+    >,
+) {
+    let (camera_transform, projection) = camera.single();
+    let view = View::new(camera_transform, projection);
+    let (
+        player,
+        (mut arrow_transform, mut arrow_visibility),
+        (mut text_transform, mut text_visibility, mut text),
+    ) = match (
+        player.get_single(),
+        arrow.get_single_mut(),
+        distance.get_single_mut(),
+    ) {
+        (Ok(player), Ok(arrow), Ok(distance)) => (player.translation.truncate(), arrow, distance),
+        _ => return,
+    };
+    // Point at the closest goal
+    let goal = goals
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .min_by(|a, b| a.distance(player).partial_cmp(&b.distance(player)).unwrap());
+
+    match goal {
+        Some(goal) if !view.contains(goal) => {
+            *arrow_transform = view.get_arrow_transform(goal);
+
+            // Show the distance in tiles just inside of the arrow
+            let inwards = (view.center - goal).normalize_or_zero() * ARROW_SIZE * view.scale;
+            text_transform.translation = (view.get_edge_point(goal) + inwards).extend(10.0);
+            text_transform.scale = Vec3::splat(view.scale);
+            let tiles = goal.distance(player) / Tile::SIZE;
+            text.sections[0].value = format!("{tiles:.0}");
+
+            arrow_visibility.is_visible = true;
+            text_visibility.is_visible = true;
+        }
+        _ => {
+            arrow_visibility.is_visible = false;
+            text_visibility.is_visible = false;
+        }
+    }
+}
+
+fn update_missile_indicators(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    camera: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    projectiles: Query<(&Transform, &Projectile), Without<MissileIndicator>>,
+    mut arrows: Query<
+        (Entity, &mut Transform, &mut Sprite),
+        (With<MissileIndicator>, Without<MainCamera>),
+    >,
+) {
+    let (camera_transform, projection) = camera.single();
+    let view = View::new(camera_transform, projection);
+
+    // Every off-screen missile that is close enough to be a threat, paired with how urgent it is
+    let missiles: Vec<(Vec2, f32)> = projectiles
+        .iter()
+        .filter(|(_, projectile)| matches!(projectile, Projectile::Missile))
+        .map(|(transform, _)| transform.translation.truncate())
+        .filter(|position| !view.contains(*position))
+        .filter_map(|position| {
+            let distance_from_view = position.distance(view.get_edge_point(position));
+            let urgency = 1.0 - distance_from_view / MISSILE_WARNING_DISTANCE;
+            (urgency > 0.0).then(|| (position, urgency))
+        })
+        .collect();
+
+    // Reuse existing arrows, removing the ones that are no longer needed
+    let mut arrow_count = 0;
+    for (entity, mut transform, mut sprite) in arrows.iter_mut() {
+        match missiles.get(arrow_count) {
+            Some((position, urgency)) => {
+                *transform = view.get_arrow_transform(*position);
+                sprite.color = get_urgency_color(*urgency);
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+        arrow_count += 1;
+    }
+
+    // Spawn arrows for missiles that do not have one yet
+    for (position, urgency) in missiles.iter().skip(arrow_count) {
+        let mut bundle = arrow_bundle(&asset_server, get_urgency_color(*urgency));
+        bundle.transform = view.get_arrow_transform(*position);
+        bundle.visibility.is_visible = true;
+        commands.spawn_bundle(bundle).insert(MissileIndicator);
+    }
+}
+
+// Fade from yellow to red as a missile gets closer
+fn get_urgency_color(urgency: f32) -> Color {
+    let urgency = urgency.clamp(0.0, 1.0);
+    Color::rgb(1.0, 0.9 * (1.0 - urgency), 0.1)
+}
+
+// Indicators stay in the world after the game ends, so make sure they do not linger on screen
+fn hide_indicators(
+    mut indicators: Query<
+        &mut Visibility,
+        Or<(
+            With<GoalIndicator>,
+            With<GoalDistance>,
+            With<MissileIndicator>,
+        )>,
+    >,
+) {
+    for mut visibility in indicators.iter_mut() {
+        visibility.is_visible = false;
+    }
+}
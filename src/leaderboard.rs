@@ -0,0 +1,291 @@
+use crate::{
+    level_pack::LevelPacks,
+    storage,
+    ui::{
+        spawn_back_button,
+        widgets::{spawn_label, spawn_menu_screen, spawn_scroll_list, spawn_text, spawn_title},
+        GameFont, ACTIVE_BUTTON_COLOR,
+    },
+    upgrades::{Upgrade, UpgradeTracker},
+    util::{format_day, get_current_day},
+    world::GameWorld,
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const PLAYER_NAME_FILE: &str = "player_name.ron";
+const MAX_ENTRIES: usize = 10;
+// Width of each column of the leaderboard table, in percent
+const COLUMN_WIDTHS: [f32; 7] = [6.0, 18.0, 11.0, 11.0, 26.0, 15.0, 13.0];
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerName>().add_system_set(
+            SystemSet::on_enter(AppState::Leaderboard).with_system(create_leaderboard),
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub time: f32,
+    pub score: u32,
+    pub primary: Option<Upgrade>,
+    pub secondary: Option<Upgrade>,
+    // Date the run was completed: YYYY-MM-DD
+    pub date: String,
+    // Version of the game the run was played on
+    pub version: String,
+    // Content hash of the version of the level the run was played on
+    #[serde(default)]
+    pub level_hash: Option<String>,
+}
+
+impl LeaderboardEntry {
+    // Entry for a run finished today with the current loadout, the name is filled in later
+    pub fn new(time: f32, score: u32, upgrades: &UpgradeTracker, level_hash: String) -> Self {
+        Self {
+            name: String::new(),
+            time,
+            score,
+            primary: upgrades.primary,
+            secondary: upgrades.secondary,
+            date: format_day(get_current_day()),
+            version: env!("CARGO_PKG_VERSION").into(),
+            level_hash: Some(level_hash),
+        }
+    }
+
+    // Whether the run was played on a version of the level other than the one with the given hash
+    fn is_stale(&self, level_hash: &str) -> bool {
+        self.level_hash
+            .as_ref()
+            .map_or(false, |entry_hash| entry_hash != level_hash)
+    }
+
+    fn get_loadout(&self) -> String {
+        let name =
+            |upgrade: Option<Upgrade>| upgrade.map_or("-".into(), |upgrade| upgrade.to_string());
+        format!("{} / {}", name(self.primary), name(self.secondary))
+    }
+}
+
+// Fastest runs of a level, fastest first
+#[derive(Default, Serialize, Deserialize)]
+pub struct Leaderboard(pub Vec<LeaderboardEntry>);
+
+impl Leaderboard {
+    pub fn load(level_id: &str) -> Self {
+        storage::load(&Self::get_file_name(level_id))
+    }
+
+    // Every level has its own file, named after the level id with every byte other than ASCII
+    // letters, digits, - and _ percent-encoded so different ids never share a file
+    fn get_file_name(level_id: &str) -> String {
+        let name: String = level_id
+            .bytes()
+            .map(|byte| match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (byte as char).to_string(),
+                _ => format!("%{byte:02X}"),
+            })
+            .collect();
+        format!("leaderboards/{name}.ron")
+    }
+
+    // Whether a run with the given time would make it onto the leaderboard
+    pub fn qualifies(&self, time: f32) -> bool {
+        self.0.len() < MAX_ENTRIES || self.0.iter().any(|entry| time < entry.time)
+    }
+
+    // Add a run and save the leaderboard, returns the entry's position if it made the cut
+    pub fn add(&mut self, level_id: &str, entry: LeaderboardEntry) -> Option<usize> {
+        // Runs tied with an earlier one are placed after it
+        let position = self
+            .0
+            .iter()
+            .position(|other| entry.time < other.time)
+            .unwrap_or(self.0.len());
+        if position >= MAX_ENTRIES {
+            return None;
+        }
+        self.0.insert(position, entry);
+        self.0.truncate(MAX_ENTRIES);
+
+        if let Err(error) = storage::save(&Self::get_file_name(level_id), self) {
+            warn!("Failed to save leaderboard: {error}");
+        }
+        Some(position)
+    }
+}
+
+// Name entered for the last leaderboard entry, suggested for the next one
+#[derive(Default, Serialize, Deserialize)]
+pub struct PlayerName(pub String);
+
+impl PlayerName {
+    // Name to use for a run given what was typed into a name field, keeping the suggested name
+    // when nothing was typed
+    pub fn get_entered(&self, typed: &str) -> String {
+        if !typed.is_empty() {
+            typed.into()
+        } else if !self.0.is_empty() {
+            self.0.clone()
+        } else {
+            "Anonymous".into()
+        }
+    }
+
+    // Suggestion shown in an empty name field
+    pub fn get_placeholder(&self) -> &str {
+        if self.0.is_empty() {
+            "Type your name"
+        } else {
+            &self.0
+        }
+    }
+
+    pub fn set(&mut self, name: String) {
+        self.0 = name;
+        if let Err(error) = storage::save(PLAYER_NAME_FILE, self) {
+            warn!("Failed to save player name: {error}");
+        }
+    }
+}
+
+impl FromWorld for PlayerName {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load(PLAYER_NAME_FILE)
+    }
+}
+
+// Level whose leaderboard is shown, must be inserted before entering the leaderboard
+pub struct LeaderboardLevel {
+    pub level_id: String,
+    pub name: String,
+    // Content hash of the current version of the level, runs set on other versions are grayed out
+    pub level_hash: Option<String>,
+    // Position of the entry that was just added, which is highlighted
+    pub highlight: Option<usize>,
+    // Screen that the back button leads to
+    pub previous_state: AppState,
+}
+
+impl LeaderboardLevel {
+    // Leaderboard of a level in a pack, which leads back to the pack's level select
+    pub fn for_pack_level(
+        packs: &LevelPacks,
+        pack: usize,
+        index: usize,
+        highlight: Option<usize>,
+    ) -> Self {
+        Self {
+            level_id: packs.0[pack].get_level_id(index),
+            name: packs.get_level(pack, index).name.clone(),
+            level_hash: GameWorld::load_level(packs, pack, index)
+                .ok()
+                .map(|world| world.get_level_hash()),
+            highlight,
+            previous_state: AppState::LevelSelect,
+        }
+    }
+}
+
+fn spawn_table_row(parent: &mut ChildBuilder, font: &GameFont, cells: [String; 7], color: Color) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(45.0)),
+                flex_shrink: 0.0,
+                align_items: AlignItems::Center,
+                ..Style::default()
+            },
+            color: Color::NONE.into(),
+            ..NodeBundle::default()
+        })
+        .with_children(|parent| {
+            for (cell, width) in cells.into_iter().zip(COLUMN_WIDTHS) {
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(width), Val::Percent(100.0)),
+                            align_items: AlignItems::Center,
+                            ..Style::default()
+                        },
+                        color: Color::NONE.into(),
+                        ..NodeBundle::default()
+                    })
+                    .with_children(|parent| {
+                        spawn_text(parent, font, 28.0, [(cell, color)]);
+                    });
+            }
+        });
+}
+
+fn create_leaderboard(mut commands: Commands, font: Res<GameFont>, level: Res<LeaderboardLevel>) {
+    let leaderboard = Leaderboard::load(&level.level_id);
+    let is_stale = |entry: &LeaderboardEntry| {
+        level
+            .level_hash
+            .as_ref()
+            .map_or(false, |level_hash| entry.is_stale(level_hash))
+    };
+
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    spawn_back_button(&mut commands, font.get_handle());
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, &level.name);
+
+        spawn_scroll_list(
+            parent,
+            Size::new(Val::Percent(90.0), Val::Percent(75.0)),
+            |parent| {
+                let header =
+                    ["#", "Name", "Time", "Score", "Loadout", "Date", "Version"].map(String::from);
+                spawn_table_row(parent, &font, header, ACTIVE_BUTTON_COLOR);
+
+                for (position, entry) in leaderboard.0.iter().enumerate() {
+                    let cells = [
+                        (position + 1).to_string(),
+                        entry.name.clone(),
+                        format!("{:.2}", entry.time),
+                        entry.score.to_string(),
+                        entry.get_loadout(),
+                        entry.date.clone(),
+                        entry.version.clone(),
+                    ];
+                    let color = if level.highlight == Some(position) {
+                        ACTIVE_BUTTON_COLOR
+                    } else if is_stale(entry) {
+                        Color::GRAY
+                    } else {
+                        Color::WHITE
+                    };
+                    spawn_table_row(parent, &font, cells, color);
+                }
+
+                if leaderboard.0.is_empty() {
+                    spawn_label(parent, &font, "No runs yet", 30.0);
+                }
+            },
+        )
+        .insert(UiColor(Color::rgba(0.0, 0.0, 0.0, 0.3)));
+
+        if leaderboard.0.iter().any(is_stale) {
+            spawn_text(
+                parent,
+                &font,
+                30.0,
+                [(
+                    "Runs in gray were set before the level was changed".to_string(),
+                    Color::GRAY,
+                )],
+            );
+        }
+    });
+}
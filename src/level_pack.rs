@@ -0,0 +1,214 @@
+use crate::{medal::MedalTimes, storage, upgrades::Upgrade, world::LEVELS};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+// Name of the manifest file that describes the levels of a pack and their order
+const MANIFEST_FILE: &str = "pack.ron";
+
+pub enum LevelSource {
+    // Level bundled into the game binary
+    Embedded(&'static str),
+    // Level loaded from a file at runtime
+    File(PathBuf),
+}
+
+impl LevelSource {
+    // Debug builds read built-in levels from the assets directory so that they can be reloaded
+    // while playing, falling back to the copy in the binary if the file isn't there
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    fn builtin(file: &str, contents: &'static str) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("assets/levels")
+                .join(file);
+            if path.exists() {
+                return LevelSource::File(path);
+            }
+        }
+        LevelSource::Embedded(contents)
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            LevelSource::Embedded(contents) => Ok(contents.to_string()),
+            LevelSource::File(path) => fs::read_to_string(path),
+        }
+    }
+
+    // When the level's file was last changed, embedded levels never change
+    pub fn get_modified(&self) -> Option<SystemTime> {
+        match self {
+            LevelSource::Embedded(_) => None,
+            LevelSource::File(path) => fs::metadata(path).and_then(|meta| meta.modified()).ok(),
+        }
+    }
+}
+
+pub struct LevelInfo {
+    pub name: String,
+    pub source: LevelSource,
+    pub medal_times: Option<MedalTimes>,
+    pub author: Option<String>,
+    // Upgrades that may be used on the level, any upgrade is allowed when this is None
+    pub allowed_upgrades: Option<Vec<Upgrade>>,
+}
+
+pub struct LevelPack {
+    pub name: String,
+    pub levels: Vec<LevelInfo>,
+}
+
+impl LevelPack {
+    fn builtin() -> Self {
+        Self {
+            name: "Beeline".into(),
+            levels: LEVELS
+                .iter()
+                .map(|(name, file, contents, medal_times)| LevelInfo {
+                    name: name.to_string(),
+                    source: LevelSource::builtin(file, contents),
+                    medal_times: Some(*medal_times),
+                    author: None,
+                    allowed_upgrades: None,
+                })
+                .collect(),
+        }
+    }
+
+    // Load a pack from a directory, ordered by its manifest if one exists, otherwise by file name
+    fn load(directory: &Path) -> io::Result<Self> {
+        let manifest_path = directory.join(MANIFEST_FILE);
+        if manifest_path.exists() {
+            let manifest: PackManifest = ron::from_str(&fs::read_to_string(&manifest_path)?)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            return Ok(Self {
+                name: manifest.name,
+                levels: manifest
+                    .levels
+                    .into_iter()
+                    .map(|level| LevelInfo {
+                        name: level.name,
+                        source: LevelSource::File(directory.join(level.file)),
+                        medal_times: level.medal_times,
+                        author: level.author,
+                        allowed_upgrades: level.allowed_upgrades,
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .map_or(false, |extension| extension == "tsv")
+            })
+            .collect();
+        paths.sort();
+
+        Ok(Self {
+            name: file_name(directory),
+            levels: paths
+                .into_iter()
+                .map(|path| LevelInfo {
+                    name: path
+                        .file_stem()
+                        .map_or_else(String::new, |stem| stem.to_string_lossy().into()),
+                    source: LevelSource::File(path),
+                    medal_times: None,
+                    author: None,
+                    allowed_upgrades: None,
+                })
+                .collect(),
+        })
+    }
+
+    // Unique identifier of a level, used as the key for saved records
+    pub fn get_level_id(&self, index: usize) -> String {
+        format!("{}/{}", self.name, self.levels[index].name)
+    }
+}
+
+#[derive(Deserialize)]
+struct PackManifest {
+    name: String,
+    levels: Vec<ManifestLevel>,
+}
+
+#[derive(Deserialize)]
+struct ManifestLevel {
+    name: String,
+    // Path to the level file, relative to the pack directory
+    file: PathBuf,
+    #[serde(default)]
+    medal_times: Option<MedalTimes>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    allowed_upgrades: Option<Vec<Upgrade>>,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into())
+}
+
+// Every level pack available, starting with the packs bundled into the game
+pub struct LevelPacks(pub Vec<LevelPack>);
+
+impl LevelPacks {
+    // Directories that are searched for level packs, each subdirectory is a pack
+    fn get_pack_directories() -> Vec<PathBuf> {
+        let mut directories = vec![PathBuf::from("assets/packs")];
+        if let Some(dir) = storage::data_dir() {
+            directories.push(dir.join("packs"));
+        }
+        directories
+    }
+
+    pub fn load() -> Self {
+        let mut packs = vec![LevelPack::builtin()];
+
+        for directory in Self::get_pack_directories() {
+            let mut pack_directories: Vec<PathBuf> = match fs::read_dir(&directory) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_dir())
+                    .collect(),
+                Err(_) => continue,
+            };
+            pack_directories.sort();
+
+            for pack_directory in pack_directories {
+                match LevelPack::load(&pack_directory) {
+                    Ok(pack) if !pack.levels.is_empty() => packs.push(pack),
+                    Ok(_) => {}
+                    Err(error) => {
+                        warn!("Failed to load pack {}: {error}", pack_directory.display());
+                    }
+                }
+            }
+        }
+
+        Self(packs)
+    }
+
+    pub fn get_level(&self, pack: usize, index: usize) -> &LevelInfo {
+        &self.0[pack].levels[index]
+    }
+}
+
+impl FromWorld for LevelPacks {
+    fn from_world(_world: &mut World) -> Self {
+        Self::load()
+    }
+}
+
+// The pack currently being browsed in level select
+pub struct SelectedPack(pub usize);
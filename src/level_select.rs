@@ -1,20 +1,40 @@
 use crate::{
-    ui::{spawn_back_button, GameFont},
-    world::{GameWorld, LEVELS},
+    collectible::RunProgress,
+    leaderboard::LeaderboardLevel,
+    level_pack::{LevelInfo, LevelPacks, SelectedPack},
+    records::LevelRecords,
+    thumbnail::LevelThumbnails,
+    ui::{
+        spawn_back_button,
+        widgets::{
+            list_item_size, spawn_button, spawn_disabled_button_with, spawn_label,
+            spawn_list_button, spawn_menu_screen, spawn_scroll_list, spawn_text, spawn_text_input,
+            spawn_title, TextInput,
+        },
+        FocusedButton, GameFont, ACTIVE_BUTTON_COLOR,
+    },
+    world::GameWorld,
     AppState,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::FocusPolicy};
 
 pub struct LevelSelectPlugin;
 
 impl Plugin for LevelSelectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(
-            SystemSet::on_enter(AppState::LevelSelect).with_system(create_level_select),
-        )
-        .add_system_set(
-            SystemSet::on_update(AppState::LevelSelect).with_system(manage_level_select_buttons),
-        );
+        // Level packs must be loaded before their thumbnails can be rendered
+        app.init_resource::<LevelPacks>()
+            .init_resource::<LevelThumbnails>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::LevelSelect).with_system(create_level_select),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::LevelSelect)
+                    .with_system(manage_level_select_buttons)
+                    .with_system(manage_leaderboard_button)
+                    .with_system(search_levels)
+                    .with_system(update_level_details),
+            );
     }
 }
 
@@ -23,107 +43,429 @@ struct LevelSelectButton {
     level: usize,
 }
 
-fn create_level_select(mut commands: Commands, font: Res<GameFont>) {
+// Every entry in the level list, including locked levels that can not be clicked
+#[derive(Component)]
+struct LevelEntry(usize);
+
+// Node that holds the level list, the list is rebuilt whenever the search changes
+#[derive(Component)]
+struct LevelList;
+
+// Text input used to filter the levels by name or author
+#[derive(Component)]
+struct LevelSearch;
+
+fn matches_search(info: &LevelInfo, search: &str) -> bool {
+    let search = search.to_lowercase();
+    info.name.to_lowercase().contains(&search)
+        || info
+            .author
+            .as_ref()
+            .map_or(false, |author| author.to_lowercase().contains(&search))
+}
+
+// Level shown in the details panel
+struct DetailsLevel(Option<usize>);
+
+#[derive(Component)]
+struct DetailsPanel;
+
+#[derive(Component)]
+struct DetailsThumbnail;
+
+// Opens the leaderboard of the level shown in the details panel, spawned once a level is shown
+#[derive(Component)]
+struct LeaderboardButton;
+
+#[derive(Component)]
+struct DetailsText;
+
+fn create_level_select(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    packs: Res<LevelPacks>,
+    selected_pack: Res<SelectedPack>,
+    records: Res<LevelRecords>,
+    thumbnails: Res<LevelThumbnails>,
+) {
+    let pack = selected_pack.0;
+    let level_pack = &packs.0[pack];
+    commands.insert_resource(DetailsLevel(None));
     commands.spawn_bundle(UiCameraBundle::default());
 
     spawn_back_button(&mut commands, font.get_handle());
 
-    commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
-                ..Style::default()
-            },
-            color: Color::NONE.into(),
-            ..NodeBundle::default()
-        })
-        .with_children(|parent| {
-            const LEVEL_SELECT_HEIGHT: f32 = 70.0;
-            // Spawn level select title
-            parent.spawn_bundle(TextBundle {
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, &level_pack.name);
+
+        parent
+            .spawn_bundle(NodeBundle {
                 style: Style {
-                    position_type: PositionType::Absolute,
-                    position: Rect {
-                        top: Val::Percent(2.0),
+                    size: Size::new(Val::Percent(90.0), Val::Percent(80.0)),
+                    margin: Rect {
+                        top: Val::Percent(12.0),
                         ..Rect::default()
                     },
+                    justify_content: JustifyContent::SpaceBetween,
                     ..Style::default()
                 },
-                text: Text::with_section(
-                    "Level Select",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 70.0,
-                        ..TextStyle::default()
-                    },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
-            });
-
-            // Spawn level selector
-            parent
-                .spawn_bundle(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(60.0), Val::Percent(LEVEL_SELECT_HEIGHT)),
-                        margin: Rect::all(Val::Auto),
-                        flex_direction: FlexDirection::ColumnReverse,
-                        ..Style::default()
-                    },
-                    color: Color::GRAY.into(),
-                    ..NodeBundle::default()
-                })
-                .with_children(|parent| {
-                    // Set button height dynamically (based on number of levels)
-                    let button_height = LEVEL_SELECT_HEIGHT / LEVELS.len() as f32;
-
-                    for (level, (name, _)) in LEVELS.iter().enumerate() {
+                color: Color::NONE.into(),
+                ..NodeBundle::default()
+            })
+            .with_children(|parent| {
+                // Search box and level list on the left
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(58.0), Val::Percent(100.0)),
+                            flex_direction: FlexDirection::ColumnReverse,
+                            ..Style::default()
+                        },
+                        color: Color::NONE.into(),
+                        ..NodeBundle::default()
+                    })
+                    .with_children(|parent| {
+                        spawn_text_input(parent, &font, "Press / to search", false)
+                            .insert(LevelSearch);
                         parent
-                            .spawn_bundle(ButtonBundle {
+                            .spawn_bundle(NodeBundle {
                                 style: Style {
-                                    size: Size::new(
-                                        Val::Percent(80.0),
-                                        Val::Percent(button_height),
-                                    ),
-                                    margin: Rect::all(Val::Auto),
-                                    justify_content: JustifyContent::Center,
-                                    align_items: AlignItems::Center,
+                                    size: Size::new(Val::Percent(100.0), Val::Percent(90.0)),
                                     ..Style::default()
                                 },
-                                ..ButtonBundle::default()
+                                color: Color::NONE.into(),
+                                ..NodeBundle::default()
                             })
-                            .insert(LevelSelectButton { level })
+                            .insert(LevelList)
                             .with_children(|parent| {
-                                parent.spawn_bundle(TextBundle {
-                                    text: Text::with_section(
-                                        *name,
-                                        TextStyle {
-                                            font: font.get_handle(),
-                                            font_size: 30.0,
-                                            color: Color::BLACK,
-                                        },
-                                        TextAlignment::default(),
-                                    ),
-                                    ..TextBundle::default()
-                                });
+                                spawn_level_list(
+                                    parent,
+                                    &font,
+                                    &packs,
+                                    pack,
+                                    &records,
+                                    &thumbnails,
+                                    "",
+                                );
                             });
+                    });
+
+                // Details of the hovered level on the right
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(38.0), Val::Percent(100.0)),
+                            flex_direction: FlexDirection::ColumnReverse,
+                            align_items: AlignItems::Center,
+                            padding: Rect::all(Val::Px(15.0)),
+                            ..Style::default()
+                        },
+                        color: Color::rgba(0.0, 0.0, 0.0, 0.3).into(),
+                        ..NodeBundle::default()
+                    })
+                    .insert(DetailsPanel)
+                    .with_children(|parent| {
+                        parent
+                            .spawn_bundle(ImageBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                                    margin: Rect {
+                                        bottom: Val::Px(15.0),
+                                        ..Rect::default()
+                                    },
+                                    display: Display::None,
+                                    ..Style::default()
+                                },
+                                ..ImageBundle::default()
+                            })
+                            .insert(DetailsThumbnail);
+                        spawn_text(parent, &font, 30.0, []).insert(DetailsText);
+                    });
+            });
+    });
+}
+
+fn spawn_level_list(
+    parent: &mut ChildBuilder,
+    font: &GameFont,
+    packs: &LevelPacks,
+    pack: usize,
+    records: &LevelRecords,
+    thumbnails: &LevelThumbnails,
+    search: &str,
+) {
+    let level_pack = &packs.0[pack];
+    spawn_scroll_list(
+        parent,
+        Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+        |parent| {
+            let mut found = false;
+            for (level, info) in level_pack.levels.iter().enumerate() {
+                if !matches_search(info, search) {
+                    continue;
+                }
+                found = true;
+
+                let unlocked = records.is_unlocked(level_pack, level);
+                let medal = records.get_medal(level_pack, level);
+                let name = if unlocked {
+                    info.name.clone()
+                } else {
+                    format!("{} (Locked)", info.name)
+                };
+
+                let contents = |parent: &mut ChildBuilder| {
+                    // Spawn level thumbnail to the left of the level name
+                    if let Some(thumbnail) = thumbnails.get_handle(pack, level) {
+                        parent.spawn_bundle(ImageBundle {
+                            style: Style {
+                                size: Size::new(Val::Auto, Val::Percent(80.0)),
+                                margin: Rect {
+                                    right: Val::Px(20.0),
+                                    ..Rect::default()
+                                },
+                                ..Style::default()
+                            },
+                            image: thumbnail.into(),
+                            focus_policy: FocusPolicy::Pass,
+                            ..ImageBundle::default()
+                        });
                     }
-                });
-        });
+                    spawn_text(
+                        parent,
+                        font,
+                        30.0,
+                        [
+                            (name, Color::BLACK),
+                            // Show the best medal earned on the level in its color
+                            (
+                                medal.map_or_else(String::new, |medal| format!("   {medal}")),
+                                medal.map_or(Color::BLACK, |medal| medal.color()),
+                            ),
+                        ],
+                    );
+                };
+
+                // Locked levels are displayed without being clickable, but can still be hovered to
+                // see their details
+                if unlocked {
+                    spawn_list_button(parent, contents)
+                        .insert(LevelSelectButton { level })
+                        .insert(LevelEntry(level));
+                } else {
+                    spawn_disabled_button_with(parent, list_item_size(), contents)
+                        .insert(Interaction::default())
+                        .insert(LevelEntry(level));
+                }
+            }
+
+            if !found {
+                spawn_label(parent, font, "No levels found", 30.0);
+            }
+        },
+    );
 }
 
 fn manage_level_select_buttons(
     mut commands: Commands,
     mut state: ResMut<State<AppState>>,
+    packs: Res<LevelPacks>,
+    selected_pack: Res<SelectedPack>,
     interaction: Query<(&Interaction, &LevelSelectButton), (Changed<Interaction>, With<Button>)>,
 ) {
     for (interaction, level_select_button) in interaction.iter() {
         // Check if the button has been clicked
         if matches!(interaction, Interaction::Clicked) {
-            commands.insert_resource(GameWorld::load_level(level_select_button.level).unwrap());
-            state.set(AppState::StartDelay).unwrap();
+            match GameWorld::load_level(&packs, selected_pack.0, level_select_button.level) {
+                Ok(world) => {
+                    commands.insert_resource(world);
+                    commands.insert_resource(RunProgress::default());
+                    state.set(AppState::StartDelay).unwrap();
+                }
+                Err(error) => error!("Failed to load level: {error}"),
+            }
+        }
+    }
+}
+
+fn manage_leaderboard_button(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    packs: Res<LevelPacks>,
+    selected_pack: Res<SelectedPack>,
+    details_level: Res<DetailsLevel>,
+    interaction: Query<&Interaction, (Changed<Interaction>, With<LeaderboardButton>)>,
+) {
+    for interaction in interaction.iter() {
+        if let (Interaction::Clicked, Some(level)) = (interaction, details_level.0) {
+            commands.insert_resource(LeaderboardLevel::for_pack_level(
+                &packs,
+                selected_pack.0,
+                level,
+                None,
+            ));
+            state.set(AppState::Leaderboard).unwrap();
+        }
+    }
+}
+
+fn search_levels(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    packs: Res<LevelPacks>,
+    selected_pack: Res<SelectedPack>,
+    records: Res<LevelRecords>,
+    thumbnails: Res<LevelThumbnails>,
+    search: Query<(&TextInput, ChangeTrackers<TextInput>), With<LevelSearch>>,
+    list: Query<(Entity, &Children), With<LevelList>>,
+) {
+    let search = match search.get_single() {
+        Ok((search, tracker)) if tracker.is_changed() && !tracker.is_added() => search,
+        _ => return,
+    };
+
+    for (entity, children) in list.iter() {
+        for child in children.iter() {
+            commands.entity(*child).despawn_recursive();
+        }
+        commands.entity(entity).with_children(|parent| {
+            spawn_level_list(
+                parent,
+                &font,
+                &packs,
+                selected_pack.0,
+                &records,
+                &thumbnails,
+                &search.value,
+            );
+        });
+    }
+}
+
+fn update_level_details(
+    mut commands: Commands,
+    mut details_level: ResMut<DetailsLevel>,
+    font: Res<GameFont>,
+    packs: Res<LevelPacks>,
+    selected_pack: Res<SelectedPack>,
+    records: Res<LevelRecords>,
+    thumbnails: Res<LevelThumbnails>,
+    focused_button: Res<FocusedButton>,
+    entries: Query<(Entity, &Interaction, &LevelEntry)>,
+    mut thumbnail: Query<(&mut UiImage, &mut Style), With<DetailsThumbnail>>,
+    mut text: Query<&mut Text, With<DetailsText>>,
+    panel: Query<Entity, With<DetailsPanel>>,
+    leaderboard_button: Query<(), With<LeaderboardButton>>,
+) {
+    // Show the hovered level, falling back to the one focused with the keyboard
+    let level = entries
+        .iter()
+        .find(|(_, interaction, _)| !matches!(interaction, Interaction::None))
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|(entity, _, _)| focused_button.is_focused(*entity))
+        })
+        .map(|(_, _, entry)| entry.0);
+    if level.is_none() || level == details_level.0 {
+        return;
+    }
+    details_level.0 = level;
+    let level = level.unwrap();
+
+    let pack = selected_pack.0;
+    let level_pack = &packs.0[pack];
+    let info = &level_pack.levels[level];
+
+    if let Ok((mut image, mut style)) = thumbnail.get_single_mut() {
+        match thumbnails.get_handle(pack, level) {
+            Some(handle) => {
+                *image = handle.into();
+                style.display = Display::Flex;
+            }
+            None => style.display = Display::None,
+        }
+    }
+
+    let style = TextStyle {
+        font: font.get_handle(),
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+    let section = |value: String, color: Color| TextSection {
+        value,
+        style: TextStyle {
+            color,
+            ..style.clone()
+        },
+    };
+    let mut sections = vec![section(format!("{}\n", info.name), ACTIVE_BUTTON_COLOR)];
+    let best_time = records.get_best_time(level_pack, level);
+    let assisted_best_time = records.get_assisted_best_time(level_pack, level);
+    if let Some(time) = best_time {
+        sections.push(section(format!("Best time {time:.2}\n"), Color::WHITE));
+    }
+    if let Some(time) = assisted_best_time {
+        sections.push(section(
+            format!("Assisted best time {time:.2}\n"),
+            Color::WHITE,
+        ));
+    }
+    if best_time.is_none() && assisted_best_time.is_none() {
+        sections.push(section("Not completed\n".into(), Color::WHITE));
+    }
+    if let Some(score) = records.get_best_score(level_pack, level) {
+        sections.push(section(format!("Best score {score}\n"), Color::WHITE));
+    }
+    // Hashing the level tells whether the records above were set on the current version of it
+    let level_hash = GameWorld::load_level(&packs, pack, level)
+        .ok()
+        .map(|world| world.get_level_hash());
+    if let Some(level_hash) = &level_hash {
+        if records.is_stale(level_pack, level, level_hash) {
+            sections.push(section(
+                "Records set before the level changed\n".into(),
+                Color::GRAY,
+            ));
+        } else if let Some(time) = records.get_stale_best_time(level_pack, level) {
+            sections.push(section(
+                format!("Best time before the level changed {time:.2}\n"),
+                Color::GRAY,
+            ));
+        }
+    }
+    if let Some(medal) = records.get_medal(level_pack, level) {
+        sections.push(section(format!("{medal} medal\n"), medal.color()));
+    }
+    if let Some(author) = &info.author {
+        sections.push(section(format!("By {author}\n"), Color::WHITE));
+    }
+    let allowed_upgrades = match &info.allowed_upgrades {
+        None => "Any".into(),
+        Some(upgrades) if upgrades.is_empty() => "None".into(),
+        Some(upgrades) => upgrades
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(", "),
+    };
+    sections.push(section(
+        format!("Upgrades: {allowed_upgrades}"),
+        Color::WHITE,
+    ));
+    if let Some(level_hash) = level_hash {
+        sections.push(section(format!("\nLevel hash {level_hash}"), Color::GRAY));
+    }
+
+    for mut text in text.iter_mut() {
+        text.sections = sections.clone();
+    }
+
+    if leaderboard_button.iter().next().is_none() {
+        for panel in panel.iter() {
+            commands.entity(panel).with_children(|parent| {
+                spawn_button(parent, &font, "Leaderboard").insert(LeaderboardButton);
+            });
         }
     }
 }
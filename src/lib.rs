@@ -0,0 +1,4 @@
+// Code shared between the game and the leaderboard_server binary
+pub mod online {
+    pub mod backend;
+}
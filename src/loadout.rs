@@ -0,0 +1,75 @@
+use crate::{
+    storage,
+    upgrades::{Upgrade, UpgradeTracker},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const PRESETS_FILE: &str = "loadout_presets.ron";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LoadoutPreset {
+    pub name: String,
+    pub primary: Option<Upgrade>,
+    pub secondary: Option<Upgrade>,
+}
+
+// Named loadouts saved by the player, in the order they were created
+#[derive(Default, Serialize, Deserialize)]
+pub struct LoadoutPresets(pub Vec<LoadoutPreset>);
+
+impl LoadoutPresets {
+    // Saving under the name of an existing preset replaces it
+    pub fn add(&mut self, name: String, upgrades: &UpgradeTracker) {
+        let preset = LoadoutPreset {
+            name,
+            primary: upgrades.primary,
+            secondary: upgrades.secondary,
+        };
+        match self.0.iter_mut().find(|other| other.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.0.push(preset),
+        }
+        self.save();
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.0.len() {
+            self.0.remove(index);
+            self.save();
+        }
+    }
+
+    pub fn apply(&self, index: usize, upgrades: &mut UpgradeTracker) {
+        if let Some(preset) = self.0.get(index) {
+            let loadout = UpgradeTracker {
+                primary: preset.primary,
+                secondary: preset.secondary,
+            };
+            match loadout.validate() {
+                Ok(()) => *upgrades = loadout,
+                Err(error) => warn!("Invalid loadout preset {}: {error}", preset.name),
+            }
+        }
+    }
+
+    // Name used when the player saves a preset without typing one
+    pub fn get_default_name(&self) -> String {
+        (1..)
+            .map(|number| format!("Preset {number}"))
+            .find(|name| self.0.iter().all(|preset| preset.name != *name))
+            .unwrap()
+    }
+
+    fn save(&self) {
+        if let Err(error) = storage::save(PRESETS_FILE, self) {
+            warn!("Failed to save loadout presets: {error}");
+        }
+    }
+}
+
+impl FromWorld for LoadoutPresets {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load(PRESETS_FILE)
+    }
+}
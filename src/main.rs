@@ -1,17 +1,39 @@
 #![allow(clippy::type_complexity)]
 
+mod accessibility;
+mod assist;
 mod camera;
+mod collectible;
 mod collision;
+mod daily;
 mod death;
+#[cfg(feature = "debug-overlay")]
+mod debug;
 mod enemy;
 mod game_overlay;
+mod generator;
 mod help;
+mod indicator;
+mod leaderboard;
+mod level_pack;
 mod level_select;
+mod loadout;
+mod medal;
 mod menu;
+mod minimap;
+mod online;
+mod pack_select;
 mod player;
 mod pursue;
+mod records;
+mod replay;
 mod retry;
+mod score;
+mod settings;
+mod sound;
 mod start_delay;
+mod storage;
+mod thumbnail;
 mod ui;
 mod upgrade_select;
 mod upgrades;
@@ -21,12 +43,22 @@ mod world;
 
 use benimator::AnimationPlugin;
 use bevy::prelude::*;
+use std::{env, path::Path, process};
 
+use accessibility::AccessibilityPlugin;
+use assist::AssistPlugin;
 use camera::CameraPlugin;
-use collision::CollisionPlugin;
+use collectible::CollectiblePlugin;
 use death::DeathPlugin;
 use enemy::EnemyPlugin;
+use generator::GeneratorParams;
+use indicator::IndicatorPlugin;
+use online::OnlinePlugin;
 use player::PlayerPlugin;
+use replay::ReplayPlugin;
+use score::ScorePlugin;
+use settings::Settings;
+use sound::SoundPlugin;
 use start_delay::StartDelayPlugin;
 use ui::UiPlugins;
 use upgrades::UpgradesPlugin;
@@ -36,13 +68,18 @@ use world::WorldPlugin;
 pub enum AppState {
     Menu,
     UpgradeSelect,
+    PackSelect,
     LevelSelect,
     Help,
+    Settings,
+    Assist,
+    Daily,
     Game,
     StartDelay,
     Death,
     Retry,
     Victory,
+    Leaderboard,
 }
 
 pub fn despawn_all(mut commands: Commands, entities: Query<Entity>) {
@@ -51,26 +88,85 @@ pub fn despawn_all(mut commands: Commands, entities: Query<Entity>) {
     }
 }
 
+// Print why a command line mode failed and exit with a failure status
+fn exit_with_error(message: String) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
 fn main() {
-    App::new()
+    // Render level thumbnails instead of launching the game:
+    // beeline --export-thumbnails <directory> [tile size]
+    let args: Vec<String> = env::args().collect();
+    if let Some(index) = args.iter().position(|arg| arg == "--export-thumbnails") {
+        let directory = args.get(index + 1).map_or("thumbnails", String::as_str);
+        let tile_size = args.get(index + 2).map_or(24, |tile_size| {
+            tile_size
+                .parse()
+                .unwrap_or_else(|_| exit_with_error(format!("Invalid tile size: {tile_size}")))
+        });
+        if let Err(error) = thumbnail::export_thumbnails(Path::new(directory), tile_size) {
+            exit_with_error(format!("Failed to export thumbnails: {error}"));
+        }
+        return;
+    }
+
+    // Generate a level file to start designing from instead of launching the game:
+    // beeline --generate-level <file> [seed] [option=value ...]
+    if let Some(index) = args.iter().position(|arg| arg == "--generate-level") {
+        let path = args.get(index + 1).map_or("generated.tsv", String::as_str);
+        let seed = args.get(index + 2).map_or(0, |seed| {
+            seed.parse()
+                .unwrap_or_else(|_| exit_with_error(format!("Invalid seed: {seed}")))
+        });
+        let options = args.get(index + 3..).unwrap_or_default();
+        let params = GeneratorParams::parse(options)
+            .unwrap_or_else(|error| exit_with_error(error.to_string()));
+        if let Err(error) = generator::export_level(Path::new(path), seed, &params) {
+            exit_with_error(format!("Failed to export level: {error}"));
+        }
+        return;
+    }
+
+    let settings = Settings::load();
+
+    let mut app = App::new();
+    app.insert_resource(settings.get_window_descriptor())
+        .insert_resource(settings)
         .add_plugins(DefaultPlugins)
         .add_state(AppState::Menu)
         .add_system_set(SystemSet::on_exit(AppState::Menu).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::UpgradeSelect).with_system(despawn_all))
+        .add_system_set(SystemSet::on_exit(AppState::PackSelect).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::LevelSelect).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::Help).with_system(despawn_all))
+        .add_system_set(SystemSet::on_exit(AppState::Settings).with_system(despawn_all))
+        .add_system_set(SystemSet::on_exit(AppState::Assist).with_system(despawn_all))
+        .add_system_set(SystemSet::on_exit(AppState::Daily).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::Retry).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::Victory).with_system(despawn_all))
+        .add_system_set(SystemSet::on_exit(AppState::Leaderboard).with_system(despawn_all))
         .add_system_set(SystemSet::on_exit(AppState::StartDelay).with_system(despawn_all))
         .add_plugin(AnimationPlugin::default())
+        .add_plugin(AccessibilityPlugin)
+        .add_plugin(AssistPlugin)
         .add_plugin(CameraPlugin)
-        .add_plugin(CollisionPlugin)
+        .add_plugin(CollectiblePlugin)
         .add_plugin(EnemyPlugin)
+        .add_plugin(IndicatorPlugin)
+        .add_plugin(OnlinePlugin)
         .add_plugin(PlayerPlugin)
+        .add_plugin(ReplayPlugin)
+        .add_plugin(ScorePlugin)
+        .add_plugin(SoundPlugin)
         .add_plugins(UiPlugins)
         .add_plugin(UpgradesPlugin)
         .add_plugin(WorldPlugin)
         .add_plugin(DeathPlugin)
-        .add_plugin(StartDelayPlugin)
-        .run();
+        .add_plugin(StartDelayPlugin);
+
+    #[cfg(feature = "debug-overlay")]
+    app.add_plugin(debug::DebugPlugin);
+
+    app.run();
 }
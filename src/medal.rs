@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use strum_macros::{Display, EnumIter};
+
+#[derive(Debug, Display, EnumIter, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl Medal {
+    pub fn color(&self) -> Color {
+        match self {
+            Medal::Bronze => Color::rgb(0.8, 0.5, 0.2),
+            Medal::Silver => Color::rgb(0.75, 0.75, 0.75),
+            Medal::Gold => Color::rgb(1.0, 0.84, 0.0),
+            Medal::Platinum => Color::rgb(0.6, 0.9, 1.0),
+        }
+    }
+}
+
+// Maximum completion time in seconds required to earn each medal on a level
+#[derive(Debug, Deserialize, Copy, Clone)]
+pub struct MedalTimes {
+    pub bronze: f32,
+    pub silver: f32,
+    pub gold: f32,
+    pub platinum: f32,
+}
+
+impl MedalTimes {
+    pub const fn new(bronze: f32, silver: f32, gold: f32, platinum: f32) -> Self {
+        Self {
+            bronze,
+            silver,
+            gold,
+            platinum,
+        }
+    }
+
+    pub fn get_medal(&self, time: f32) -> Option<Medal> {
+        if time <= self.platinum {
+            Some(Medal::Platinum)
+        } else if time <= self.gold {
+            Some(Medal::Gold)
+        } else if time <= self.silver {
+            Some(Medal::Silver)
+        } else if time <= self.bronze {
+            Some(Medal::Bronze)
+        } else {
+            None
+        }
+    }
+}
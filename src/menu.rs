@@ -1,11 +1,18 @@
 use crate::{
+    level_pack::LevelPacks,
+    medal::Medal,
     player::Player,
-    ui::GameFont,
+    records::LevelRecords,
+    ui::{
+        widgets::{spawn_button, spawn_label, spawn_menu_screen, spawn_text},
+        GameFont,
+    },
     util::{AnimatedSprite, AnimatedSpriteData},
     AppState,
 };
 use benimator::SpriteSheetAnimation;
 use bevy::prelude::*;
+use strum::IntoEnumIterator;
 
 pub struct MenuPlugin;
 
@@ -19,8 +26,11 @@ impl Plugin for MenuPlugin {
 #[derive(Component)]
 enum ButtonType {
     Play,
+    Daily,
     Upgrades,
+    Assist,
     Help,
+    Settings,
 }
 
 fn create_menu(
@@ -29,6 +39,8 @@ fn create_menu(
     mut animations: ResMut<Assets<SpriteSheetAnimation>>,
     mut textures: ResMut<Assets<TextureAtlas>>,
     asset_server: Res<AssetServer>,
+    packs: Res<LevelPacks>,
+    records: Res<LevelRecords>,
 ) {
     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
 
@@ -49,123 +61,72 @@ fn create_menu(
     commands.spawn_bundle(UiCameraBundle::default());
     commands.insert_resource(ClearColor(Color::rgb(0.2, 0.2, 0.4)));
 
-    commands.spawn_bundle(TextBundle {
-        style: Style {
-            position_type: PositionType::Absolute,
-            position: Rect {
-                left: Val::Percent(10.0),
-                bottom: Val::Percent(60.0),
-                ..Rect::default()
-            },
-            ..Style::default()
-        },
-        text: Text::with_section(
-            "Beeline",
-            TextStyle {
-                font: font.get_handle(),
-                font_size: 130.0,
-                ..TextStyle::default()
-            },
-            TextAlignment::default(),
-        ),
-        ..TextBundle::default()
-    });
-
-    commands
-        .spawn_bundle(ButtonBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    left: Val::Percent(10.0),
-                    bottom: Val::Percent(45.0),
-                    ..Rect::default()
-                },
-                size: Size::new(Val::Px(200.0), Val::Px(65.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..Style::default()
-            },
-            ..ButtonBundle::default()
+    // Count the best medal earned on every level
+    let medals: Vec<Medal> = packs
+        .0
+        .iter()
+        .flat_map(|pack| {
+            let records = &records;
+            (0..pack.levels.len()).filter_map(move |index| records.get_medal(pack, index))
         })
-        .insert(ButtonType::Play)
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                text: Text::with_section(
-                    "Play",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 60.0,
-                        color: Color::BLACK,
-                    },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
-            });
-        });
+        .collect();
 
-    commands
-        .spawn_bundle(ButtonBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    left: Val::Percent(10.0),
-                    bottom: Val::Percent(30.0),
-                    ..Rect::default()
-                },
-                size: Size::new(Val::Px(200.0), Val::Px(65.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..Style::default()
-            },
-            ..ButtonBundle::default()
-        })
-        .insert(ButtonType::Upgrades)
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                text: Text::with_section(
-                    "Upgrades",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 60.0,
-                        color: Color::BLACK,
+    let mut medal_sections = vec![("Medals".to_string(), Color::WHITE)];
+    for medal in Medal::iter().rev() {
+        let count = medals.iter().filter(|earned| **earned == medal).count();
+        medal_sections.push((format!("   {count} {medal}"), medal.color()));
+    }
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        // Title and buttons sit on the left, next to the bee
+        parent
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Percent(10.0),
+                        bottom: Val::Percent(12.0),
+                        ..Rect::default()
                     },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
+                    flex_direction: FlexDirection::ColumnReverse,
+                    align_items: AlignItems::FlexStart,
+                    ..Style::default()
+                },
+                color: Color::NONE.into(),
+                ..NodeBundle::default()
+            })
+            .with_children(|parent| {
+                spawn_label(parent, &font, "Beeline", 130.0);
+                for (button_type, text) in [
+                    (ButtonType::Play, "Play"),
+                    (ButtonType::Daily, "Daily"),
+                    (ButtonType::Upgrades, "Upgrades"),
+                    (ButtonType::Assist, "Assist"),
+                    (ButtonType::Help, "Help"),
+                    (ButtonType::Settings, "Settings"),
+                ] {
+                    spawn_button(parent, &font, text).insert(button_type);
+                }
             });
-        });
 
-    commands
-        .spawn_bundle(ButtonBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    left: Val::Percent(10.0),
-                    bottom: Val::Percent(15.0),
-                    ..Rect::default()
-                },
-                size: Size::new(Val::Px(200.0), Val::Px(65.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..Style::default()
-            },
-            ..ButtonBundle::default()
-        })
-        .insert(ButtonType::Help)
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                text: Text::with_section(
-                    "Help",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 60.0,
-                        color: Color::BLACK,
+        parent
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        right: Val::Percent(5.0),
+                        bottom: Val::Percent(5.0),
+                        ..Rect::default()
                     },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
+                    ..Style::default()
+                },
+                color: Color::NONE.into(),
+                ..NodeBundle::default()
+            })
+            .with_children(|parent| {
+                spawn_text(parent, &font, 40.0, medal_sections);
             });
-        });
+    });
 }
 
 fn manage_menu_buttons(
@@ -175,14 +136,23 @@ fn manage_menu_buttons(
     for (interaction, button_type) in interaction.iter() {
         match (interaction, button_type) {
             (Interaction::Clicked, ButtonType::Play) => {
-                state.set(AppState::LevelSelect).unwrap();
+                state.set(AppState::PackSelect).unwrap();
+            }
+            (Interaction::Clicked, ButtonType::Daily) => {
+                state.set(AppState::Daily).unwrap();
             }
             (Interaction::Clicked, ButtonType::Upgrades) => {
                 state.set(AppState::UpgradeSelect).unwrap();
             }
+            (Interaction::Clicked, ButtonType::Assist) => {
+                state.set(AppState::Assist).unwrap();
+            }
             (Interaction::Clicked, ButtonType::Help) => {
                 state.set(AppState::Help).unwrap();
             }
+            (Interaction::Clicked, ButtonType::Settings) => {
+                state.set(AppState::Settings).unwrap();
+            }
             _ => {}
         }
     }
@@ -0,0 +1,242 @@
+use crate::{
+    enemy::Projectile,
+    player::Player,
+    thumbnail::to_bevy_image,
+    world::{GameWorld, LevelReloaded, Tile},
+    AppState,
+};
+use bevy::prelude::*;
+use image::{Rgba, RgbaImage};
+
+// Size of the longest side of the minimap in pixels
+const MINIMAP_SIZE: f32 = 200.0;
+
+// Number of image pixels drawn for each tile, keeps tiles crisp when the image is scaled up
+const PIXELS_PER_TILE: u32 = 4;
+
+const MARKER_SIZE: f32 = 6.0;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShowMinimap>().add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(toggle_minimap)
+                .with_system(update_player_marker)
+                .with_system(update_missile_markers)
+                .with_system(redraw_minimap),
+        );
+    }
+}
+
+// Whether the minimap is shown, kept between levels
+pub struct ShowMinimap(bool);
+
+impl Default for ShowMinimap {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Component)]
+struct Minimap;
+
+#[derive(Component)]
+struct PlayerMarker;
+
+#[derive(Component)]
+struct MissileMarker;
+
+fn get_tile_color(tile: &Tile) -> Option<Rgba<u8>> {
+    match tile {
+        Tile::Wall => Some(Rgba([200, 200, 200, 255])),
+        Tile::Spawner(_) => Some(Rgba([220, 60, 60, 255])),
+        Tile::Trap => Some(Rgba([240, 140, 40, 255])),
+        Tile::Goal { .. } => Some(Rgba([250, 210, 120, 255])),
+        _ => None,
+    }
+}
+
+fn render_minimap(world: &GameWorld) -> RgbaImage {
+    let height = world.layout.len() as u32;
+    let width = world.layout.iter().map(Vec::len).max().unwrap_or(0) as u32;
+    let mut minimap = RgbaImage::from_pixel(
+        width.max(1) * PIXELS_PER_TILE,
+        height.max(1) * PIXELS_PER_TILE,
+        Rgba([0, 0, 0, 150]),
+    );
+
+    for (i, row) in world.layout.iter().enumerate() {
+        for (j, tile) in row.iter().enumerate() {
+            if let Some(color) = tile.as_ref().and_then(get_tile_color) {
+                for y in 0..PIXELS_PER_TILE {
+                    for x in 0..PIXELS_PER_TILE {
+                        minimap.put_pixel(
+                            j as u32 * PIXELS_PER_TILE + x,
+                            i as u32 * PIXELS_PER_TILE + y,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    minimap
+}
+
+// Size of the minimap on screen, which keeps the aspect ratio of the level
+fn get_minimap_size(minimap: &RgbaImage) -> Size<Val> {
+    let scale = MINIMAP_SIZE / minimap.width().max(minimap.height()) as f32;
+    Size::new(
+        Val::Px(minimap.width() as f32 * scale),
+        Val::Px(minimap.height() as f32 * scale),
+    )
+}
+
+// Convert a world position into a position on the minimap, as percentages from the bottom left
+fn to_minimap_position(world: &GameWorld, position: Vec2) -> Rect<Val> {
+    let (min, max) = world.get_bounds();
+    let relative = ((position - min) / (max - min)).clamp(Vec2::ZERO, Vec2::ONE);
+    Rect {
+        left: Val::Percent(relative.x * 100.0),
+        bottom: Val::Percent(relative.y * 100.0),
+        ..Rect::default()
+    }
+}
+
+fn marker_bundle(color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            size: Size::new(Val::Px(MARKER_SIZE), Val::Px(MARKER_SIZE)),
+            // Center the marker on its position
+            margin: Rect {
+                left: Val::Px(-MARKER_SIZE / 2.0),
+                bottom: Val::Px(-MARKER_SIZE / 2.0),
+                ..Rect::default()
+            },
+            ..Style::default()
+        },
+        color: color.into(),
+        ..NodeBundle::default()
+    }
+}
+
+pub fn create_minimap(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    world: &GameWorld,
+    show_minimap: &ShowMinimap,
+) {
+    let minimap = render_minimap(world);
+    let size = get_minimap_size(&minimap);
+
+    commands
+        .spawn_bundle(ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Percent(2.0),
+                    bottom: Val::Percent(2.0),
+                    ..Rect::default()
+                },
+                size,
+                display: if show_minimap.0 {
+                    Display::Flex
+                } else {
+                    Display::None
+                },
+                ..Style::default()
+            },
+            image: images.add(to_bevy_image(minimap)).into(),
+            ..ImageBundle::default()
+        })
+        .insert(Minimap)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(marker_bundle(Color::YELLOW))
+                .insert(PlayerMarker);
+        });
+}
+
+fn toggle_minimap(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut show_minimap: ResMut<ShowMinimap>,
+    mut minimap: Query<&mut Style, With<Minimap>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        show_minimap.0 = !show_minimap.0;
+        for mut style in minimap.iter_mut() {
+            style.display = if show_minimap.0 {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
+
+fn update_player_marker(
+    world: Res<GameWorld>,
+    player: Query<&Transform, With<Player>>,
+    mut marker: Query<&mut Style, With<PlayerMarker>>,
+) {
+    if let (Ok(player), Ok(mut marker)) = (player.get_single(), marker.get_single_mut()) {
+        marker.position = to_minimap_position(&world, player.translation.truncate());
+    }
+}
+
+fn update_missile_markers(
+    mut commands: Commands,
+    world: Res<GameWorld>,
+    minimap: Query<Entity, With<Minimap>>,
+    projectiles: Query<(&Transform, &Projectile)>,
+    mut markers: Query<(Entity, &mut Style), With<MissileMarker>>,
+) {
+    let minimap = match minimap.get_single() {
+        Ok(minimap) => minimap,
+        Err(_) => return,
+    };
+    let missiles: Vec<Vec2> = projectiles
+        .iter()
+        .filter(|(_, projectile)| matches!(projectile, Projectile::Missile))
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect();
+
+    // Reuse existing markers, removing the ones that are no longer needed
+    let mut marker_count = 0;
+    for (entity, mut style) in markers.iter_mut() {
+        match missiles.get(marker_count) {
+            Some(position) => style.position = to_minimap_position(&world, *position),
+            None => commands.entity(entity).despawn_recursive(),
+        }
+        marker_count += 1;
+    }
+
+    // Spawn markers for missiles that do not have one yet
+    for position in missiles.iter().skip(marker_count) {
+        let mut bundle = marker_bundle(Color::RED);
+        bundle.style.position = to_minimap_position(&world, *position);
+        let marker = commands.spawn_bundle(bundle).insert(MissileMarker).id();
+        commands.entity(minimap).push_children(&[marker]);
+    }
+}
+
+// Draw the minimap again when the level is reloaded from its file
+fn redraw_minimap(
+    mut reloaded: EventReader<LevelReloaded>,
+    world: Res<GameWorld>,
+    mut images: ResMut<Assets<Image>>,
+    mut minimap: Query<(&mut UiImage, &mut Style), With<Minimap>>,
+) {
+    if reloaded.iter().last().is_none() {
+        return;
+    }
+    for (mut image, mut style) in minimap.iter_mut() {
+        let rendered = render_minimap(&world);
+        style.size = get_minimap_size(&rendered);
+        image.0 = images.add(to_bevy_image(rendered));
+    }
+}
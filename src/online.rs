@@ -0,0 +1,64 @@
+pub use beeline::online::backend;
+
+use crate::storage;
+use backend::{FileBackend, HttpBackend, LeaderboardBackend, Submission};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::Arc, thread};
+
+const ONLINE_FILE: &str = "online.ron";
+
+pub struct OnlinePlugin;
+
+impl Plugin for OnlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OnlineLeaderboard>();
+    }
+}
+
+// Where runs are submitted, set in online.ron in the config directory
+#[derive(Serialize, Deserialize)]
+enum BackendConfig {
+    Off,
+    File(PathBuf),
+    Http(String),
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        BackendConfig::Off
+    }
+}
+
+// Online leaderboard that runs are submitted to, if one is set up
+pub struct OnlineLeaderboard(Option<Arc<dyn LeaderboardBackend>>);
+
+impl OnlineLeaderboard {
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    // Submit in the background so that a slow server doesn't hold up the game
+    pub fn submit(&self, submission: Submission) {
+        if let Some(backend) = &self.0 {
+            let backend = backend.clone();
+            thread::spawn(move || {
+                if let Err(error) = backend.submit(&submission) {
+                    warn!("Failed to submit run: {error}");
+                }
+            });
+        }
+    }
+}
+
+impl FromWorld for OnlineLeaderboard {
+    fn from_world(_world: &mut World) -> Self {
+        let config: BackendConfig = storage::load_config(ONLINE_FILE);
+        let backend: Option<Arc<dyn LeaderboardBackend>> = match config {
+            BackendConfig::Off => None,
+            BackendConfig::File(directory) => Some(Arc::new(FileBackend::new(directory))),
+            BackendConfig::Http(address) => Some(Arc::new(HttpBackend::new(address))),
+        };
+        Self(backend)
+    }
+}
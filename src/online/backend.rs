@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::PathBuf,
+    sync::Mutex,
+    time::Duration,
+};
+
+// Number of submissions kept for every level
+const MAX_SUBMISSIONS: usize = 100;
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+// A finished run as posted to an online leaderboard
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Submission {
+    // Content hash of the level, so that runs on edited levels go to a board of their own
+    pub level_hash: String,
+    pub name: String,
+    pub time: f32,
+    pub replay: Vec<u8>,
+}
+
+// Level hashes are written as 16 hex digits, see GameWorld::get_level_hash
+fn is_level_hash(level_hash: &str) -> bool {
+    level_hash.len() == 16 && level_hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Somewhere runs can be submitted to
+pub trait LeaderboardBackend: Send + Sync {
+    fn submit(&self, submission: &Submission) -> io::Result<()>;
+}
+
+// Keeps the submissions for every level in a file of their own
+pub struct FileBackend {
+    directory: PathBuf,
+    // Held while a file is read and written back, so submissions made at the same time from
+    // different threads don't overwrite each other
+    write_lock: Mutex<()>,
+}
+
+impl FileBackend {
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    // Fastest submissions for a level, fastest first
+    pub fn get_top(&self, level_hash: &str) -> io::Result<Vec<Submission>> {
+        match fs::read_to_string(self.get_path(level_hash)?) {
+            Ok(contents) => ron::from_str(&contents).map_err(invalid_data),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn get_path(&self, level_hash: &str) -> io::Result<PathBuf> {
+        // The hash is used as a file name, so it must not be able to point anywhere else
+        if is_level_hash(level_hash) {
+            Ok(self.directory.join(format!("{level_hash}.ron")))
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid level hash: {level_hash}"),
+            ))
+        }
+    }
+}
+
+impl LeaderboardBackend for FileBackend {
+    fn submit(&self, submission: &Submission) -> io::Result<()> {
+        // A thread that panicked while holding the lock left no half written state behind
+        let _guard = self
+            .write_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut submissions = self.get_top(&submission.level_hash)?;
+        let position = submissions
+            .iter()
+            .position(|other| submission.time < other.time)
+            .unwrap_or(submissions.len());
+        if position >= MAX_SUBMISSIONS {
+            return Ok(());
+        }
+        submissions.insert(position, submission.clone());
+        submissions.truncate(MAX_SUBMISSIONS);
+
+        let contents = ron::to_string(&submissions).map_err(invalid_data)?;
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.get_path(&submission.level_hash)?, contents)
+    }
+}
+
+// Talks to a leaderboard server over HTTP, such as the leaderboard_server binary
+pub struct HttpBackend {
+    // Host and port of the server, e.g. 127.0.0.1:7878
+    address: String,
+}
+
+impl HttpBackend {
+    pub fn new(address: String) -> Self {
+        Self { address }
+    }
+
+    // Send a request and return the body of the response
+    fn request(&self, method: &str, path: &str, body: &str) -> io::Result<String> {
+        let mut stream = TcpStream::connect(&self.address)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/ron\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.address,
+            body.len(),
+        )?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| invalid_data("Malformed response"))?;
+        // Status line: HTTP/1.1 200 OK
+        let status = head.split(' ').nth(1).unwrap_or_default();
+        if status == "200" {
+            Ok(body.to_string())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Server responded with {status}: {body}"),
+            ))
+        }
+    }
+}
+
+impl LeaderboardBackend for HttpBackend {
+    fn submit(&self, submission: &Submission) -> io::Result<()> {
+        let body = ron::to_string(submission).map_err(invalid_data)?;
+        self.request("POST", "/submissions", &body).map(|_| ())
+    }
+}
+
+pub fn invalid_data(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
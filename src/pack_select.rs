@@ -0,0 +1,83 @@
+use crate::{
+    level_pack::{LevelPacks, SelectedPack},
+    records::LevelRecords,
+    ui::{
+        spawn_back_button,
+        widgets::{
+            spawn_list_button, spawn_menu_screen, spawn_scroll_list, spawn_text, spawn_title,
+        },
+        GameFont,
+    },
+    AppState,
+};
+use bevy::prelude::*;
+
+pub struct PackSelectPlugin;
+
+impl Plugin for PackSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(AppState::PackSelect).with_system(create_pack_select),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::PackSelect).with_system(manage_pack_select_buttons),
+        );
+    }
+}
+
+#[derive(Component)]
+struct PackSelectButton {
+    pack: usize,
+}
+
+fn create_pack_select(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    packs: Res<LevelPacks>,
+    records: Res<LevelRecords>,
+) {
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    spawn_back_button(&mut commands, font.get_handle());
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Level Packs");
+        spawn_scroll_list(
+            parent,
+            Size::new(Val::Percent(60.0), Val::Percent(70.0)),
+            |parent| {
+                for (pack, level_pack) in packs.0.iter().enumerate() {
+                    // Show how many levels of the pack have been completed
+                    let completed = records.get_completed_count(level_pack);
+                    let total = level_pack.levels.len();
+
+                    spawn_list_button(parent, |parent| {
+                        spawn_text(
+                            parent,
+                            &font,
+                            30.0,
+                            [(
+                                format!("{}   {completed}/{total}", level_pack.name),
+                                Color::BLACK,
+                            )],
+                        );
+                    })
+                    .insert(PackSelectButton { pack });
+                }
+            },
+        );
+    });
+}
+
+fn manage_pack_select_buttons(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    interaction: Query<(&Interaction, &PackSelectButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, pack_select_button) in interaction.iter() {
+        if matches!(interaction, Interaction::Clicked) {
+            commands.insert_resource(SelectedPack(pack_select_button.pack));
+            state.set(AppState::LevelSelect).unwrap();
+        }
+    }
+}
@@ -1,14 +1,17 @@
 use crate::{
+    assist::{AssistSettings, GameTime},
     camera::MainCamera,
-    enemy::Enemy,
+    collectible::LockedGoal,
+    collision::Hitbox,
+    enemy::{Enemy, Projectile},
+    sound::{PlaySound, SoundEffect},
     upgrades::{Upgrade, UpgradeTracker},
     util::{polar_to_cartesian, AnimatedSprite, AnimatedSpriteData},
     world::Goal,
     AppState,
 };
 use benimator::SpriteSheetAnimation;
-use bevy::prelude::*;
-use impacted::CollisionShape;
+use bevy::{prelude::*, utils::HashSet};
 use std::f32::consts::PI;
 
 pub struct PlayerPlugin;
@@ -16,6 +19,14 @@ pub struct PlayerPlugin;
 #[derive(Component)]
 pub struct InvincibilityTimer(Timer);
 
+impl InvincibilityTimer {
+    // Seconds until the player can be hit, zero once the timer has finished
+    #[cfg(feature = "debug-overlay")]
+    pub fn get_remaining_secs(&self) -> f32 {
+        self.0.duration().as_secs_f32() - self.0.elapsed_secs()
+    }
+}
+
 impl Default for InvincibilityTimer {
     fn default() -> Self {
         Self(Timer::from_seconds(0.2, false))
@@ -24,16 +35,20 @@ impl Default for InvincibilityTimer {
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(
-            SystemSet::on_enter(AppState::Game).with_system(create_invincibility_timer),
-        )
-        .add_system_set(
-            SystemSet::on_update(AppState::Game)
-                .with_system(tick_invincibility_timer)
-                .with_system(move_player)
-                .with_system(detect_collision)
-                .with_system(teleport),
-        );
+        app.add_event::<NearMiss>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::Game)
+                    .with_system(create_invincibility_timer)
+                    .with_system(create_near_miss_candidates),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(tick_invincibility_timer)
+                    .with_system(move_player)
+                    .with_system(detect_collision)
+                    .with_system(detect_near_misses)
+                    .with_system(teleport),
+            );
     }
 }
 
@@ -41,7 +56,15 @@ fn create_invincibility_timer(mut commands: Commands) {
     commands.insert_resource(InvincibilityTimer::default());
 }
 
-fn tick_invincibility_timer(time: Res<Time>, mut timer: ResMut<InvincibilityTimer>) {
+// Projectiles within near miss distance of the player that haven't hit it
+#[derive(Default)]
+struct NearMissCandidates(HashSet<Entity>);
+
+fn create_near_miss_candidates(mut commands: Commands) {
+    commands.insert_resource(NearMissCandidates::default());
+}
+
+fn tick_invincibility_timer(time: Res<GameTime>, mut timer: ResMut<InvincibilityTimer>) {
     timer.0.tick(time.delta());
 }
 
@@ -51,8 +74,19 @@ pub struct Player;
 impl Player {
     pub const SIZE: f32 = 24.0;
     const VELOCITY: f32 = 375.0;
+    // Projectiles whose hitbox passes within this distance of the player's count as a near miss
+    const NEAR_MISS_DISTANCE: f32 = 20.0;
+    // Distance teleported without a mouse
+    const TELEPORT_DISTANCE: f32 = 200.0;
 }
 
+// Sent once for every projectile that narrowly misses the player
+pub struct NearMiss;
+
+// Marks projectiles that have already hit or narrowly missed the player
+#[derive(Component)]
+struct Missed;
+
 // Spawn the player in the given start location
 // This function should only be called by the world plugin
 pub fn spawn_player(
@@ -78,11 +112,8 @@ pub fn spawn_player(
         ..Transform::default()
     };
 
-    let collision_shape = if upgrades.has_upgrade(Upgrade::Shrink) {
-        CollisionShape::new_circle((Player::SIZE - 8.) / 4.0)
-    } else {
-        CollisionShape::new_circle((Player::SIZE - 8.) / 2.0)
-    };
+    // Shrinks along with the player's scale
+    let hitbox = Hitbox::Circle((Player::SIZE - 8.) / 2.0);
 
     // Spawn player
     commands
@@ -98,30 +129,74 @@ pub fn spawn_player(
                 ..AnimatedSpriteData::default()
             },
         ))
-        .insert(collision_shape)
+        .insert(hitbox)
         .insert(Player);
 }
 
+// Direction to move in from the arrow keys, WASD or a gamepad's left stick
+fn get_input_direction(
+    keyboard_input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    axes: &Axis<GamepadAxis>,
+) -> Vec2 {
+    let key_axis = |positive: [KeyCode; 2], negative: [KeyCode; 2]| {
+        let pressed = |keys: [KeyCode; 2]| keys.iter().any(|key| keyboard_input.pressed(*key));
+        pressed(positive) as i32 as f32 - pressed(negative) as i32 as f32
+    };
+    let mut direction = Vec2::new(
+        key_axis([KeyCode::Right, KeyCode::D], [KeyCode::Left, KeyCode::A]),
+        key_axis([KeyCode::Up, KeyCode::W], [KeyCode::Down, KeyCode::S]),
+    );
+
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| axes.get(GamepadAxis(*gamepad, axis_type)).unwrap_or(0.0);
+        direction += Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+    }
+
+    direction.clamp_length_max(1.0)
+}
+
 fn move_player(
     windows: Res<Windows>,
-    time: Res<Time>,
+    time: Res<GameTime>,
     upgrades: Res<UpgradeTracker>,
-    camera: Query<&Camera, With<MainCamera>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    camera: Query<(&Camera, &Transform, &OrthographicProjection), With<MainCamera>>,
     mut transform: Query<&mut Transform, (With<Player>, Without<MainCamera>)>,
 ) {
-    let camera = camera.single();
+    let (camera, camera_transform, projection) = camera.single();
     let window = windows.get(camera.window).unwrap();
-    // Some(_) if the cursor is in the window
-    if let Some(cursor_pos) = window.cursor_position() {
-        let relative_pos = Vec2::new(
-            cursor_pos.x - window.width() / 2.,
-            cursor_pos.y - window.height() / 2.,
-        );
-        let velocity_angle = relative_pos.y.atan2(relative_pos.x);
-        let magnitude_cap = window.width().min(window.height()) / 4.;
-        // between 0 and 1
-        let velocity_scale = relative_pos.length().min(magnitude_cap) / magnitude_cap;
+    let mut transform = transform.single_mut();
 
+    // Keyboard and gamepad input take priority over the mouse
+    let input_direction = get_input_direction(&keyboard_input, &gamepads, &axes);
+    let movement = if input_direction != Vec2::ZERO {
+        Some((
+            input_direction.y.atan2(input_direction.x),
+            input_direction.length(),
+        ))
+    } else {
+        // Some(_) if the cursor is in the window
+        window.cursor_position().map(|cursor_pos| {
+            // The camera does not always stay centered on the player, so steer relative to where
+            // the player is drawn on screen
+            let player_screen_pos = Vec2::new(window.width(), window.height()) / 2.
+                + (transform.translation - camera_transform.translation).truncate()
+                    / projection.scale;
+            let relative_pos = cursor_pos - player_screen_pos;
+            let magnitude_cap = window.width().min(window.height()) / 4.;
+            // between 0 and 1
+            let velocity_scale = relative_pos.length().min(magnitude_cap) / magnitude_cap;
+            (relative_pos.y.atan2(relative_pos.x), velocity_scale)
+        })
+    };
+
+    if let Some((velocity_angle, velocity_scale)) = movement {
         let velocity = polar_to_cartesian(velocity_angle, velocity_scale * Player::VELOCITY)
             * time.delta_seconds()
             * if upgrades.has_upgrade(Upgrade::DoubleSpeed) {
@@ -131,7 +206,6 @@ fn move_player(
                 1.0
             };
 
-        let mut transform = transform.single_mut();
         transform.translation.x += velocity.x;
         transform.translation.y += velocity.y;
 
@@ -141,21 +215,27 @@ fn move_player(
 
 fn detect_collision(
     invincibility_timer: Res<InvincibilityTimer>,
+    assists: Res<AssistSettings>,
     mut state: ResMut<State<AppState>>,
-    enemies: Query<&CollisionShape, With<Enemy>>,
-    goals: Query<&CollisionShape, With<Goal>>,
-    player: Query<&CollisionShape, With<Player>>,
+    mut sounds: EventWriter<PlaySound>,
+    enemies: Query<(&Hitbox, &GlobalTransform), With<Enemy>>,
+    goals: Query<(&Hitbox, &GlobalTransform), (With<Goal>, Without<LockedGoal>)>,
+    player: Query<(&Hitbox, &GlobalTransform), With<Player>>,
 ) {
     if invincibility_timer.0.finished() {
-        if let Ok(player) = player.get_single() {
-            for enemy in enemies.iter() {
-                if player.is_collided_with(enemy) {
-                    state.set(AppState::Death).unwrap();
-                    return;
+        if let Ok((player, player_transform)) = player.get_single() {
+            // Enemies can't hurt the player with the invincibility assist
+            if !assists.invincible {
+                for (enemy, transform) in enemies.iter() {
+                    if enemy.is_collided_with(transform, player, player_transform) {
+                        state.set(AppState::Death).unwrap();
+                        return;
+                    }
                 }
             }
-            for goal in goals.iter() {
-                if player.is_collided_with(goal) {
+            for (goal, transform) in goals.iter() {
+                if goal.is_collided_with(transform, player, player_transform) {
+                    sounds.send(PlaySound::new(SoundEffect::Goal));
                     state.set(AppState::Victory).unwrap();
                     return;
                 }
@@ -164,29 +244,98 @@ fn detect_collision(
     }
 }
 
+fn detect_near_misses(
+    mut commands: Commands,
+    assists: Res<AssistSettings>,
+    mut candidates: ResMut<NearMissCandidates>,
+    mut near_misses: EventWriter<NearMiss>,
+    projectiles: Query<(Entity, &Hitbox, &GlobalTransform), (With<Projectile>, Without<Missed>)>,
+    player: Query<(&Hitbox, &GlobalTransform), With<Player>>,
+) {
+    let (player, player_transform) = match player.get_single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+
+    let mut in_range = HashSet::default();
+    for (projectile, hitbox, transform) in projectiles.iter() {
+        if hitbox.is_collided_with(transform, player, player_transform) {
+            // A projectile that hits the player is never a near miss
+            candidates.0.remove(&projectile);
+            commands.entity(projectile).insert(Missed);
+        } else if hitbox.is_within(
+            transform,
+            player,
+            player_transform,
+            Player::NEAR_MISS_DISTANCE,
+        ) {
+            in_range.insert(projectile);
+        }
+    }
+
+    // Candidates are only known to have missed once they leave the range or despawn. Flying
+    // through projectiles with the invincibility assist doesn't count.
+    for projectile in candidates.0.difference(&in_range) {
+        if projectiles.get(*projectile).is_ok() {
+            commands.entity(*projectile).insert(Missed);
+        }
+        if !assists.invincible {
+            near_misses.send(NearMiss);
+        }
+    }
+    candidates.0 = in_range;
+}
+
+#[allow(clippy::too_many_arguments)]
 fn teleport(
     windows: Res<Windows>,
     camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     keyboard_input: Res<Input<KeyCode>>,
     button_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
     upgrades: Res<UpgradeTracker>,
+    mut sounds: EventWriter<PlaySound>,
     mut player: Query<&mut Transform, With<Player>>,
 ) {
-    if upgrades.was_upgrade_activated(keyboard_input, button_input, Upgrade::Teleport) {
-        let (camera, camera_transform) = camera.single();
-        let window = windows.get(camera.window).unwrap();
+    if !upgrades.was_upgrade_activated(
+        &keyboard_input,
+        &button_input,
+        &gamepad_input,
+        &gamepads,
+        Upgrade::Teleport,
+    ) {
+        return;
+    }
+
+    let (camera, camera_transform) = camera.single();
+    let window = windows.get(camera.window).unwrap();
+    let mut player_transform = player.single_mut();
+    let clicked = button_input.get_just_pressed().next().is_some();
 
-        if let Some(cursor_pos) = window.cursor_position() {
-            // Calculate the cursor's world position
+    let world_pos = match window.cursor_position() {
+        // A click teleports to the cursor's world position
+        Some(cursor_pos) if clicked => {
             let window_size = Vec2::new(window.width() as f32, window.height() as f32);
             let ndc = (cursor_pos / window_size) * 2.0 - Vec2::ONE;
             let ndc_to_world =
                 camera_transform.compute_matrix() * camera.projection_matrix.inverse();
-            let world_pos = ndc_to_world.project_point3(ndc.extend(-1.0)).truncate();
-
-            // Set player translation to the cursor's world position
-            let mut player_transform = player.single_mut();
-            player_transform.translation = world_pos.extend(player_transform.translation.z);
+            ndc_to_world.project_point3(ndc.extend(-1.0)).truncate()
         }
-    }
+        // Keys and gamepad buttons teleport a fixed distance in the direction being steered, or
+        // the one the player is facing when standing still
+        _ => {
+            let input_direction = get_input_direction(&keyboard_input, &gamepads, &axes);
+            let direction = if input_direction != Vec2::ZERO {
+                input_direction.normalize()
+            } else {
+                (player_transform.rotation * Vec3::Y).truncate()
+            };
+            player_transform.translation.truncate() + direction * Player::TELEPORT_DISTANCE
+        }
+    };
+
+    player_transform.translation = world_pos.extend(player_transform.translation.z);
+    sounds.send(PlaySound::new(SoundEffect::Teleport));
 }
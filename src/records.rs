@@ -0,0 +1,131 @@
+use crate::{level_pack::LevelPack, medal::Medal, storage};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const RECORDS_FILE: &str = "records.ron";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelRecord {
+    pub best_time: Option<f32>,
+    pub most_nectar: usize,
+    // Best time of runs that used assists, kept apart so they don't count towards medals
+    pub assisted_best_time: Option<f32>,
+    // Best score of runs without assists
+    pub best_score: Option<u32>,
+    // Content hash of the version of the level the records were set on, missing for records kept
+    // from before hashes were stored
+    pub level_hash: Option<String>,
+    // Records of the version of the level played before this one, kept when the level changes
+    pub stale_record: Option<Box<LevelRecord>>,
+}
+
+// Results of a completed run of a level
+pub struct RunResult {
+    pub time: f32,
+    pub nectar: usize,
+    pub score: u32,
+    pub assisted: bool,
+    pub level_hash: String,
+}
+
+// Best results achieved on each completed level, keyed by level id
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LevelRecords(HashMap<String, LevelRecord>);
+
+impl LevelRecords {
+    // Record a completed run of a level, returns true if it is a new best time
+    pub fn record(&mut self, level_id: String, result: &RunResult) -> bool {
+        let record = self.0.entry(level_id).or_default();
+        // Records set on another version of the level can't be compared with this run, so they
+        // are kept aside as stale and this version gets records of its own, or gets its old ones
+        // back if the level was changed back. Records without a hash are assumed to be from this
+        // version.
+        if matches!(&record.level_hash, Some(level_hash) if *level_hash != result.level_hash) {
+            let previous = record.stale_record.take();
+            let stale = std::mem::take(record);
+            *record = match previous {
+                Some(previous) if previous.level_hash.as_ref() == Some(&result.level_hash) => {
+                    *previous
+                }
+                _ => LevelRecord::default(),
+            };
+            record.stale_record = Some(Box::new(stale));
+        }
+        record.level_hash = Some(result.level_hash.clone());
+        record.most_nectar = record.most_nectar.max(result.nectar);
+        if !result.assisted {
+            record.best_score = record.best_score.max(Some(result.score));
+        }
+
+        let best_time = if result.assisted {
+            &mut record.assisted_best_time
+        } else {
+            &mut record.best_time
+        };
+        let is_best = best_time.map_or(true, |best_time| result.time < best_time);
+        if is_best {
+            *best_time = Some(result.time);
+        }
+
+        if let Err(error) = storage::save(RECORDS_FILE, self) {
+            warn!("Failed to save records: {error}");
+        }
+        is_best
+    }
+
+    // The first level of a pack is always unlocked, the rest are unlocked by completing the
+    // level before it
+    pub fn is_unlocked(&self, pack: &LevelPack, index: usize) -> bool {
+        index == 0 || self.0.contains_key(&pack.get_level_id(index - 1))
+    }
+
+    // Whether the records of a level were set on a version of the level other than the one with
+    // the given hash
+    pub fn is_stale(&self, pack: &LevelPack, index: usize, level_hash: &str) -> bool {
+        self.0
+            .get(&pack.get_level_id(index))
+            .and_then(|record| record.level_hash.as_ref())
+            .map_or(false, |record_hash| record_hash != level_hash)
+    }
+
+    pub fn get_best_time(&self, pack: &LevelPack, index: usize) -> Option<f32> {
+        self.0.get(&pack.get_level_id(index))?.best_time
+    }
+
+    pub fn get_best_score(&self, pack: &LevelPack, index: usize) -> Option<u32> {
+        self.0.get(&pack.get_level_id(index))?.best_score
+    }
+
+    // Best time set on the version of the level before the current records
+    pub fn get_stale_best_time(&self, pack: &LevelPack, index: usize) -> Option<f32> {
+        self.0
+            .get(&pack.get_level_id(index))?
+            .stale_record
+            .as_ref()?
+            .best_time
+    }
+
+    pub fn get_assisted_best_time(&self, pack: &LevelPack, index: usize) -> Option<f32> {
+        self.0.get(&pack.get_level_id(index))?.assisted_best_time
+    }
+
+    // Best medal earned on a level, if the level has medal times
+    pub fn get_medal(&self, pack: &LevelPack, index: usize) -> Option<Medal> {
+        let best_time = self.get_best_time(pack, index)?;
+        pack.levels[index].medal_times?.get_medal(best_time)
+    }
+
+    pub fn get_completed_count(&self, pack: &LevelPack) -> usize {
+        (0..pack.levels.len())
+            .filter(|index| self.0.contains_key(&pack.get_level_id(*index)))
+            .count()
+    }
+}
+
+impl FromWorld for LevelRecords {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load(RECORDS_FILE)
+    }
+}
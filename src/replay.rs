@@ -0,0 +1,63 @@
+use crate::{collectible::RunProgress, game_overlay::GameTimer, player::Player, AppState};
+use bevy::prelude::*;
+
+// Seconds between recorded positions of the bee
+const SAMPLE_INTERVAL: f32 = 1.0 / 20.0;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Replay>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(rewind_replay))
+            .add_system_set(SystemSet::on_update(AppState::Game).with_system(record_replay));
+    }
+}
+
+// Path the bee took through a run, sampled at a fixed rate: (time, position)
+#[derive(Default, Clone)]
+pub struct Replay {
+    frames: Vec<(f32, Vec2)>,
+}
+
+impl Replay {
+    fn record(&mut self, time: f32, position: Vec2) {
+        let due = self
+            .frames
+            .last()
+            .map_or(true, |(last_time, _)| time - last_time >= SAMPLE_INTERVAL);
+        if due {
+            self.frames.push((time, position));
+        }
+    }
+
+    // Drop everything recorded after the given time
+    fn truncate(&mut self, time: f32) {
+        self.frames.retain(|(frame_time, _)| *frame_time <= time);
+    }
+
+    // Every frame as three little endian floats: time, x, y
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.frames
+            .iter()
+            .flat_map(|(time, position)| [*time, position.x, position.y])
+            .flat_map(f32::to_le_bytes)
+            .collect()
+    }
+}
+
+// Start recording from where the attempt starts, which is the beginning of the level for a new run
+// or the last checkpoint reached when respawning
+fn rewind_replay(progress: Res<RunProgress>, mut replay: ResMut<Replay>) {
+    replay.truncate(progress.checkpoint_time);
+}
+
+fn record_replay(
+    mut replay: ResMut<Replay>,
+    game_timer: Query<&GameTimer>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if let (Ok(game_timer), Ok(transform)) = (game_timer.get_single(), player.get_single()) {
+        replay.record(game_timer.elapsed_secs(), transform.translation.truncate());
+    }
+}
@@ -1,5 +1,11 @@
 use crate::{
-    ui::GameFont,
+    collectible::RunProgress,
+    daily::get_daily_world,
+    level_pack::LevelPacks,
+    ui::{
+        widgets::{spawn_button_list, spawn_menu_screen, spawn_title},
+        GameFont,
+    },
     world::{GameWorld, WorldType},
     AppState,
 };
@@ -25,115 +31,46 @@ enum ButtonType {
 fn create_retry_menu(mut commands: Commands, font: Res<GameFont>) {
     commands.spawn_bundle(UiCameraBundle::default());
 
-    commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
-                ..Style::default()
-            },
-            color: Color::NONE.into(),
-            ..NodeBundle::default()
-        })
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    position: Rect {
-                        top: Val::Percent(5.0),
-                        ..Rect::default()
-                    },
-                    ..Style::default()
-                },
-                text: Text::with_section(
-                    "You died",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 90.0,
-                        ..TextStyle::default()
-                    },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
-            });
-
-            parent
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        position_type: PositionType::Absolute,
-                        position: Rect {
-                            top: Val::Percent(40.0),
-                            ..Rect::default()
-                        },
-                        size: Size::new(Val::Px(300.0), Val::Px(65.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Style::default()
-                    },
-                    ..ButtonBundle::default()
-                })
-                .insert(ButtonType::Retry)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        text: Text::with_section(
-                            "Retry",
-                            TextStyle {
-                                font: font.get_handle(),
-                                font_size: 60.0,
-                                color: Color::BLACK,
-                            },
-                            TextAlignment::default(),
-                        ),
-                        ..TextBundle::default()
-                    });
-                });
-
-            parent
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        position_type: PositionType::Absolute,
-                        position: Rect {
-                            top: Val::Percent(60.0),
-                            ..Rect::default()
-                        },
-                        size: Size::new(Val::Px(300.0), Val::Px(65.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Style::default()
-                    },
-                    ..ButtonBundle::default()
-                })
-                .insert(ButtonType::Menu)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        text: Text::with_section(
-                            "Main Menu",
-                            TextStyle {
-                                font: font.get_handle(),
-                                font_size: 60.0,
-                                color: Color::BLACK,
-                            },
-                            TextAlignment::default(),
-                        ),
-                        ..TextBundle::default()
-                    });
-                });
-        });
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "You died");
+        spawn_button_list(
+            parent,
+            &font,
+            [
+                (ButtonType::Retry, "Retry"),
+                (ButtonType::Menu, "Main Menu"),
+            ],
+        );
+    });
 }
 
 fn manage_retry_buttons(
     mut commands: Commands,
     mut state: ResMut<State<AppState>>,
     world: Res<GameWorld>,
+    packs: Res<LevelPacks>,
+    mut progress: ResMut<RunProgress>,
     interaction: Query<(&Interaction, &ButtonType), (Changed<Interaction>, With<Button>)>,
 ) {
     for (interaction, button_type) in interaction.iter() {
         match (interaction, button_type) {
             (Interaction::Clicked, ButtonType::Retry) => {
-                if let WorldType::Level { index } = &world.world_type {
-                    commands.insert_resource(GameWorld::load_level(*index).unwrap());
+                match world.world_type {
+                    WorldType::Level { pack, index } => {
+                        commands
+                            .insert_resource(GameWorld::load_level(&packs, pack, index).unwrap());
+                    }
+                    WorldType::Daily { seed } => match get_daily_world(seed) {
+                        Ok(world) => commands.insert_resource(world),
+                        Err(error) => {
+                            error!("Failed to load level: {error}");
+                            continue;
+                        }
+                    },
+                    WorldType::Endless => {}
                 }
+                // Respawn at the last checkpoint reached
+                progress.restore_checkpoint();
 
                 state.set(AppState::StartDelay).unwrap();
             }
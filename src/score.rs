@@ -0,0 +1,68 @@
+use crate::{assist::GameTime, collectible::RunProgress, player::NearMiss, AppState};
+use bevy::prelude::*;
+
+// Points for a graze before the combo multiplier is applied
+const GRAZE_POINTS: f32 = 100.0;
+const NECTAR_POINTS: u32 = 500;
+// Runs finished in less time than this earn points for every second to spare
+const PAR_SECS: f32 = 120.0;
+const POINTS_PER_SPARE_SEC: f32 = 50.0;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GrazeCombo>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(reset_combo))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(score_grazes)
+                    .with_system(decay_combo),
+            );
+    }
+}
+
+// Multiplier for graze points, raised by every graze and falling back to 1 over time
+pub struct GrazeCombo {
+    pub multiplier: f32,
+}
+
+impl GrazeCombo {
+    const STEP: f32 = 0.5;
+    const MAX: f32 = 5.0;
+    // How much the multiplier falls every second
+    const DECAY: f32 = 0.75;
+}
+
+impl Default for GrazeCombo {
+    fn default() -> Self {
+        Self { multiplier: 1.0 }
+    }
+}
+
+// Score of a run so far, combining the time taken, grazes and nectar collected
+pub fn get_score(time: f32, progress: &RunProgress) -> u32 {
+    let time_points = ((PAR_SECS - time).max(0.0) * POINTS_PER_SPARE_SEC) as u32;
+    time_points + progress.graze_points + progress.nectar.len() as u32 * NECTAR_POINTS
+}
+
+fn reset_combo(mut combo: ResMut<GrazeCombo>) {
+    *combo = GrazeCombo::default();
+}
+
+fn score_grazes(
+    mut progress: ResMut<RunProgress>,
+    mut combo: ResMut<GrazeCombo>,
+    mut near_misses: EventReader<NearMiss>,
+) {
+    for _ in near_misses.iter() {
+        progress.graze_points += (GRAZE_POINTS * combo.multiplier) as u32;
+        combo.multiplier = (combo.multiplier + GrazeCombo::STEP).min(GrazeCombo::MAX);
+    }
+}
+
+fn decay_combo(time: Res<GameTime>, mut combo: ResMut<GrazeCombo>) {
+    if combo.multiplier > 1.0 {
+        combo.multiplier = (combo.multiplier - GrazeCombo::DECAY * time.delta_seconds()).max(1.0);
+    }
+}
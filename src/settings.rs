@@ -0,0 +1,392 @@
+use crate::{
+    accessibility::ColorblindMode,
+    sound::AudioSettings,
+    storage,
+    ui::{
+        spawn_back_button,
+        widgets::{
+            spawn_choice, spawn_menu_screen, spawn_row, spawn_scroll_list, spawn_slider,
+            spawn_title, spawn_toggle, Slider, Toggle, Tooltip,
+        },
+        GameFont,
+    },
+    AppState,
+};
+use bevy::{prelude::*, window::WindowMode};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+// Resolutions that can be picked in the settings menu: (width, height)
+const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+// Camera zoom levels that can be picked in the settings menu
+const ZOOM_LEVELS: [f32; 5] = [1.5, 1.75, 2.0, 2.5, 3.0];
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(apply_window_settings)
+            .add_system_set(SystemSet::on_enter(AppState::Settings).with_system(create_settings))
+            .add_system_set(
+                SystemSet::on_update(AppState::Settings)
+                    .with_system(manage_setting_buttons)
+                    .with_system(manage_setting_controls)
+                    .with_system(update_setting_values),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Settings).with_system(save_settings));
+    }
+}
+
+#[derive(Serialize, Deserialize, Display, EnumIter, PartialEq, Eq, Copy, Clone)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    fn get_window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub display_mode: DisplayMode,
+    // Window size in logical pixels: (width, height)
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    // How many times larger the world is drawn
+    pub camera_zoom: f32,
+    pub audio: AudioSettings,
+    pub show_timer: bool,
+    pub screen_shake: bool,
+    pub colorblind_mode: ColorblindMode,
+    // Outline the hitboxes of everything that kills the player
+    pub high_contrast: bool,
+    // Turn off camera movement and effects that are not needed to play
+    pub reduced_motion: bool,
+    // Multiplier for the size of all text
+    pub text_scale: f32,
+    // Leave the bee where it is when the level file is edited while playing, otherwise it is moved
+    // to the level's start
+    pub keep_position_on_reload: bool,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        storage::load_config(SETTINGS_FILE)
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = storage::save_config(SETTINGS_FILE, self) {
+            warn!("Failed to save settings: {error}");
+        }
+    }
+
+    pub fn get_window_descriptor(&self) -> WindowDescriptor {
+        WindowDescriptor {
+            title: "Beeline".into(),
+            width: self.resolution.0 as f32,
+            height: self.resolution.1 as f32,
+            vsync: self.vsync,
+            mode: self.display_mode.get_window_mode(),
+            ..WindowDescriptor::default()
+        }
+    }
+
+    // Scale of the orthographic projection used by the game camera
+    pub fn get_camera_scale(&self) -> f32 {
+        1.0 / self.camera_zoom
+    }
+
+    pub fn is_screen_shake_enabled(&self) -> bool {
+        self.screen_shake && !self.reduced_motion
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            display_mode: DisplayMode::Windowed,
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+            camera_zoom: 2.0,
+            audio: AudioSettings::default(),
+            show_timer: true,
+            screen_shake: true,
+            colorblind_mode: ColorblindMode::Off,
+            high_contrast: false,
+            reduced_motion: false,
+            text_scale: 1.0,
+            keep_position_on_reload: true,
+        }
+    }
+}
+
+#[derive(Component, Copy, Clone)]
+enum SettingType {
+    DisplayMode,
+    Resolution,
+    Vsync,
+    CameraZoom,
+    MasterVolume,
+    EffectsVolume,
+    MusicVolume,
+    Mute,
+    ShowTimer,
+    ScreenShake,
+    ColorblindMode,
+    HighContrast,
+    ReducedMotion,
+    TextScale,
+    KeepPositionOnReload,
+}
+
+impl SettingType {
+    const ALL: [SettingType; 15] = [
+        SettingType::DisplayMode,
+        SettingType::Resolution,
+        SettingType::Vsync,
+        SettingType::CameraZoom,
+        SettingType::MasterVolume,
+        SettingType::EffectsVolume,
+        SettingType::MusicVolume,
+        SettingType::Mute,
+        SettingType::ShowTimer,
+        SettingType::ScreenShake,
+        SettingType::ColorblindMode,
+        SettingType::HighContrast,
+        SettingType::ReducedMotion,
+        SettingType::TextScale,
+        SettingType::KeepPositionOnReload,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SettingType::DisplayMode => "Display Mode",
+            SettingType::Resolution => "Resolution",
+            SettingType::Vsync => "VSync",
+            SettingType::CameraZoom => "Camera Zoom",
+            SettingType::MasterVolume => "Master Volume",
+            SettingType::EffectsVolume => "Effects Volume",
+            SettingType::MusicVolume => "Music Volume",
+            SettingType::Mute => "Mute",
+            SettingType::ShowTimer => "Show Timer",
+            SettingType::ScreenShake => "Screen Shake",
+            SettingType::ColorblindMode => "Colorblind Mode",
+            SettingType::HighContrast => "High Contrast",
+            SettingType::ReducedMotion => "Reduced Motion",
+            SettingType::TextScale => "Text Size",
+            SettingType::KeepPositionOnReload => "Keep Position On Reload",
+        }
+    }
+
+    fn get_description(&self) -> &'static str {
+        match self {
+            SettingType::DisplayMode => "Play in a window or fill the whole screen",
+            SettingType::Resolution => "Size of the window",
+            SettingType::Vsync => "Limit the frame rate to the refresh rate of the monitor",
+            SettingType::CameraZoom => "How close the camera is to the bee",
+            SettingType::MasterVolume => "Volume of all sound",
+            SettingType::EffectsVolume => "Volume of sound effects",
+            SettingType::MusicVolume => "Volume of music",
+            SettingType::Mute => "Silence all sound, can also be toggled with M",
+            SettingType::ShowTimer => "Show the level timer while playing",
+            SettingType::ScreenShake => "Shake the camera on near misses and deaths",
+            SettingType::ColorblindMode => "Tint lasers, missiles and traps to tell them apart",
+            SettingType::HighContrast => "Outline everything that can hit the bee",
+            SettingType::ReducedMotion => {
+                "Turn off the camera pan before a level, the death explosion and screen shake"
+            }
+            SettingType::TextScale => "Size of all text",
+            SettingType::KeepPositionOnReload => {
+                "Leave the bee where it is when the level file is edited while playing"
+            }
+        }
+    }
+
+    // On/off settings, shown as toggles
+    fn get_flag<'a>(&self, settings: &'a mut Settings) -> Option<&'a mut bool> {
+        match self {
+            SettingType::Vsync => Some(&mut settings.vsync),
+            SettingType::Mute => Some(&mut settings.audio.muted),
+            SettingType::ShowTimer => Some(&mut settings.show_timer),
+            SettingType::ScreenShake => Some(&mut settings.screen_shake),
+            SettingType::HighContrast => Some(&mut settings.high_contrast),
+            SettingType::ReducedMotion => Some(&mut settings.reduced_motion),
+            SettingType::KeepPositionOnReload => Some(&mut settings.keep_position_on_reload),
+            _ => None,
+        }
+    }
+
+    // Amounts shown as sliders
+    fn get_amount<'a>(&self, settings: &'a mut Settings) -> Option<&'a mut f32> {
+        match self {
+            SettingType::MasterVolume => Some(&mut settings.audio.master_volume),
+            SettingType::EffectsVolume => Some(&mut settings.audio.effects_volume),
+            SettingType::MusicVolume => Some(&mut settings.audio.music_volume),
+            SettingType::TextScale => Some(&mut settings.text_scale),
+            _ => None,
+        }
+    }
+
+    // Slider for an amount, with its range and step size
+    fn get_slider(&self, value: f32) -> Slider {
+        match self {
+            SettingType::TextScale => Slider::new(value, 0.75, 1.5, 0.25),
+            // Volumes are changed in steps of 10%
+            _ => Slider::new(value, 0.0, 1.0, 0.1),
+        }
+    }
+
+    // Text of settings that are picked from a list of options
+    fn get_value(&self, settings: &Settings) -> String {
+        match self {
+            SettingType::DisplayMode => settings.display_mode.to_string(),
+            SettingType::Resolution => {
+                format!("{}x{}", settings.resolution.0, settings.resolution.1)
+            }
+            SettingType::CameraZoom => format!("{:.2}x", settings.camera_zoom),
+            SettingType::ColorblindMode => settings.colorblind_mode.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    // Change the setting to its next option, wrapping around after the last one
+    fn cycle(&self, settings: &mut Settings) {
+        fn next<T: PartialEq + Copy>(options: &[T], current: T) -> T {
+            let index = options.iter().position(|option| *option == current);
+            options[index.map_or(0, |index| (index + 1) % options.len())]
+        }
+        match self {
+            SettingType::DisplayMode => {
+                let modes: Vec<DisplayMode> = DisplayMode::iter().collect();
+                settings.display_mode = next(&modes, settings.display_mode);
+            }
+            SettingType::Resolution => {
+                settings.resolution = next(&RESOLUTIONS, settings.resolution)
+            }
+            SettingType::CameraZoom => {
+                settings.camera_zoom = next(&ZOOM_LEVELS, settings.camera_zoom)
+            }
+            SettingType::ColorblindMode => {
+                let modes: Vec<ColorblindMode> = ColorblindMode::iter().collect();
+                settings.colorblind_mode = next(&modes, settings.colorblind_mode);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn create_settings(mut commands: Commands, font: Res<GameFont>, settings: Res<Settings>) {
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    spawn_back_button(&mut commands, font.get_handle());
+
+    // Copied so that the values of settings can be read through the same accessors that change them
+    let mut current = settings.clone();
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Settings");
+
+        // Spawn a row for every setting, with its name on the left and its value on the right
+        spawn_scroll_list(
+            parent,
+            Size::new(Val::Percent(60.0), Val::Percent(75.0)),
+            |parent| {
+                for setting in SettingType::ALL {
+                    let mut row = if let Some(enabled) = setting.get_flag(&mut current) {
+                        spawn_toggle(parent, &font, setting.label(), *enabled, setting)
+                    } else if let Some(amount) = setting.get_amount(&mut current) {
+                        let slider = setting.get_slider(*amount);
+                        spawn_slider(parent, &font, setting.label(), slider, setting)
+                    } else {
+                        let value = setting.get_value(&current);
+                        spawn_row(parent, &font, setting.label(), |parent| {
+                            spawn_choice(parent, &font, value).insert(setting);
+                        })
+                    };
+                    row.insert(Tooltip(setting.get_description().into()));
+                }
+            },
+        )
+        .insert(UiColor(Color::NONE));
+    });
+}
+
+fn manage_setting_buttons(
+    mut settings: ResMut<Settings>,
+    interaction: Query<
+        (&Interaction, &SettingType),
+        (Changed<Interaction>, With<Button>, Without<Toggle>),
+    >,
+) {
+    for (interaction, setting) in interaction.iter() {
+        if matches!(interaction, Interaction::Clicked) {
+            setting.cycle(&mut settings);
+        }
+    }
+}
+
+fn manage_setting_controls(
+    mut settings: ResMut<Settings>,
+    toggles: Query<(&Toggle, &SettingType), Changed<Toggle>>,
+    sliders: Query<(&Slider, &SettingType), Changed<Slider>>,
+) {
+    for (toggle, setting) in toggles.iter() {
+        if let Some(enabled) = setting.get_flag(&mut settings) {
+            *enabled = toggle.0;
+        }
+    }
+    for (slider, setting) in sliders.iter() {
+        if let Some(amount) = setting.get_amount(&mut settings) {
+            *amount = slider.value;
+        }
+    }
+}
+
+fn update_setting_values(
+    settings: Res<Settings>,
+    buttons: Query<(&SettingType, &Children), (With<Button>, Without<Toggle>)>,
+    mut text: Query<&mut Text>,
+) {
+    if settings.is_changed() {
+        for (setting, children) in buttons.iter() {
+            if let Ok(mut text) = text.get_mut(children[0]) {
+                text.sections[0].value = setting.get_value(&settings);
+            }
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+fn apply_window_settings(settings: Res<Settings>, mut windows: ResMut<Windows>) {
+    if settings.is_changed() {
+        if let Some(window) = windows.get_primary_mut() {
+            // Only touch the window when something actually changed to avoid it flickering
+            let mode = settings.display_mode.get_window_mode();
+            if window.mode() != mode {
+                window.set_mode(mode);
+            }
+            let (width, height) = (settings.resolution.0 as f32, settings.resolution.1 as f32);
+            if window.requested_width() != width || window.requested_height() != height {
+                window.set_resolution(width, height);
+            }
+            if window.vsync() != settings.vsync {
+                window.set_vsync(settings.vsync);
+            }
+        }
+    }
+}
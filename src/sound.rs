@@ -0,0 +1,290 @@
+use crate::{player::Player, settings::Settings, ui::widgets::TextInput, AppState};
+use bevy::{prelude::*, utils::HashMap};
+use bevy_kira_audio::{Audio, AudioChannel, AudioPlugin, AudioSource};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+// Distance from the player at which spatialized sounds can no longer be heard
+const HEARING_DISTANCE: f32 = 600.0;
+
+// Number of channels that sound effects are spread across, since volume is set per channel
+const EFFECT_CHANNELS: usize = 8;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(AudioPlugin)
+            .add_event::<PlaySound>()
+            .init_resource::<SoundAssets>()
+            .init_resource::<SoundChannels>()
+            .add_system(play_sounds)
+            .add_system(play_button_clicks)
+            .add_system(play_state_music)
+            .add_system(update_music_volume)
+            .add_system(toggle_mute);
+    }
+}
+
+#[derive(Debug, EnumIter, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum SoundEffect {
+    MissileLaunch,
+    LaserFire,
+    Explosion,
+    Teleport,
+    Goal,
+    Click,
+    Countdown,
+    CountdownEnd,
+}
+
+impl SoundEffect {
+    fn path(&self) -> &'static str {
+        match self {
+            SoundEffect::MissileLaunch => "sounds/missile.wav",
+            SoundEffect::LaserFire => "sounds/laser.wav",
+            SoundEffect::Explosion => "sounds/explosion.wav",
+            SoundEffect::Teleport => "sounds/teleport.wav",
+            SoundEffect::Goal => "sounds/goal.wav",
+            SoundEffect::Click => "sounds/click.wav",
+            SoundEffect::Countdown => "sounds/countdown.wav",
+            SoundEffect::CountdownEnd => "sounds/countdown-end.wav",
+        }
+    }
+
+    // Minimum number of seconds between two plays of the same effect,
+    // prevents rapidly firing spawners from drowning out everything else
+    fn cooldown(&self) -> f64 {
+        match self {
+            SoundEffect::LaserFire => 0.15,
+            SoundEffect::MissileLaunch => 0.1,
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, EnumIter, PartialEq, Eq, Hash, Copy, Clone)]
+enum Music {
+    Menu,
+    Game,
+    Victory,
+}
+
+impl Music {
+    fn path(&self) -> &'static str {
+        match self {
+            Music::Menu => "music/menu.wav",
+            Music::Game => "music/game.wav",
+            Music::Victory => "music/victory.wav",
+        }
+    }
+
+    fn from_state(state: &AppState) -> Self {
+        match state {
+            AppState::StartDelay | AppState::Game | AppState::Death | AppState::Retry => {
+                Music::Game
+            }
+            AppState::Victory => Music::Victory,
+            _ => Music::Menu,
+        }
+    }
+}
+
+// Event to play a sound effect, sounds with a position are quieter the further they are from the player
+pub struct PlaySound {
+    effect: SoundEffect,
+    position: Option<Vec2>,
+}
+
+impl PlaySound {
+    pub fn new(effect: SoundEffect) -> Self {
+        Self {
+            effect,
+            position: None,
+        }
+    }
+
+    pub fn at(effect: SoundEffect, position: Vec2) -> Self {
+        Self {
+            effect,
+            position: Some(position),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub effects_volume: f32,
+    pub music_volume: f32,
+    pub muted: bool,
+}
+
+impl AudioSettings {
+    pub fn get_effects_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master_volume * self.effects_volume
+        }
+    }
+
+    pub fn get_music_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master_volume * self.music_volume
+        }
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            effects_volume: 0.8,
+            music_volume: 0.5,
+            muted: false,
+        }
+    }
+}
+
+struct SoundAssets {
+    effects: HashMap<SoundEffect, Handle<AudioSource>>,
+    music: HashMap<Music, Handle<AudioSource>>,
+}
+
+impl FromWorld for SoundAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            effects: SoundEffect::iter()
+                .map(|effect| (effect, asset_server.load(effect.path())))
+                .collect(),
+            music: Music::iter()
+                .map(|music| (music, asset_server.load(music.path())))
+                .collect(),
+        }
+    }
+}
+
+struct SoundChannels {
+    music: AudioChannel,
+    current_music: Option<Music>,
+    effects: Vec<AudioChannel>,
+    next_effect: usize,
+    // Time each effect was last played at, in seconds since startup
+    last_played: HashMap<SoundEffect, f64>,
+}
+
+impl SoundChannels {
+    fn next_effect_channel(&mut self) -> &AudioChannel {
+        let channel = &self.effects[self.next_effect];
+        self.next_effect = (self.next_effect + 1) % self.effects.len();
+        channel
+    }
+}
+
+impl Default for SoundChannels {
+    fn default() -> Self {
+        Self {
+            music: AudioChannel::new("music".into()),
+            current_music: None,
+            effects: (0..EFFECT_CHANNELS)
+                .map(|i| AudioChannel::new(format!("effects-{i}")))
+                .collect(),
+            next_effect: 0,
+            last_played: HashMap::default(),
+        }
+    }
+}
+
+fn play_sounds(
+    audio: Res<Audio>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    assets: Res<SoundAssets>,
+    mut channels: ResMut<SoundChannels>,
+    mut events: EventReader<PlaySound>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let listener = player
+        .get_single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    // Only play the loudest instance of each effect per frame
+    let mut volumes: HashMap<SoundEffect, f32> = HashMap::default();
+    for event in events.iter() {
+        let volume = match (event.position, listener) {
+            (Some(position), Some(listener)) => {
+                (1.0 - position.distance(listener) / HEARING_DISTANCE).max(0.0)
+            }
+            _ => 1.0,
+        };
+        let loudest = volumes.entry(event.effect).or_insert(0.0);
+        *loudest = loudest.max(volume);
+    }
+
+    let now = time.seconds_since_startup();
+    for (effect, volume) in volumes {
+        let on_cooldown = channels
+            .last_played
+            .get(&effect)
+            .map_or(false, |last_played| now - last_played < effect.cooldown());
+        if volume <= 0.0 || on_cooldown {
+            continue;
+        }
+        channels.last_played.insert(effect, now);
+
+        let channel = channels.next_effect_channel();
+        audio.set_volume_in_channel(settings.audio.get_effects_volume() * volume, channel);
+        audio.play_in_channel(assets.effects[&effect].clone(), channel);
+    }
+}
+
+fn play_button_clicks(
+    mut sounds: EventWriter<PlaySound>,
+    interaction: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+) {
+    if interaction
+        .iter()
+        .any(|interaction| matches!(interaction, Interaction::Clicked))
+    {
+        sounds.send(PlaySound::new(SoundEffect::Click));
+    }
+}
+
+fn play_state_music(
+    audio: Res<Audio>,
+    state: Res<State<AppState>>,
+    assets: Res<SoundAssets>,
+    mut channels: ResMut<SoundChannels>,
+) {
+    let music = Music::from_state(state.current());
+    if channels.current_music != Some(music) {
+        audio.stop_channel(&channels.music);
+        audio.play_looped_in_channel(assets.music[&music].clone(), &channels.music);
+        channels.current_music = Some(music);
+    }
+}
+
+fn update_music_volume(audio: Res<Audio>, settings: Res<Settings>, channels: Res<SoundChannels>) {
+    if settings.is_changed() {
+        audio.set_volume_in_channel(settings.audio.get_music_volume(), &channels.music);
+    }
+}
+
+fn toggle_mute(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    text_inputs: Query<&TextInput>,
+) {
+    // M can not be used while typing into a text input
+    if keyboard_input.just_pressed(KeyCode::M) && !text_inputs.iter().any(TextInput::is_focused) {
+        settings.audio.muted = !settings.audio.muted;
+        settings.save();
+    }
+}
@@ -5,8 +5,10 @@ use bevy::utils::Duration;
 
 use crate::camera::MainCamera;
 use crate::player::Player;
+use crate::settings::Settings;
+use crate::sound::{PlaySound, SoundEffect};
 use crate::ui::GameFont;
-use crate::world::spawn_world;
+use crate::world::{spawn_world, SPAWN_WORLD};
 use crate::AppState;
 
 pub struct StartDelayPlugin;
@@ -16,7 +18,7 @@ impl Plugin for StartDelayPlugin {
         app.add_system_set(
             SystemSet::on_enter(AppState::StartDelay)
                 .with_system(create_delay_timer)
-                .with_system(spawn_world),
+                .with_system(spawn_world.label(SPAWN_WORLD)),
         )
         .add_system_set(SystemSet::on_update(AppState::StartDelay).with_system(update_delay_timer));
     }
@@ -29,7 +31,13 @@ struct CameraSpeed {
     speed: Option<Vec2>,
 }
 
-fn create_delay_timer(mut commands: Commands, font: Res<GameFont>) {
+fn create_delay_timer(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    sounds.send(PlaySound::new(SoundEffect::Countdown));
+
     commands.spawn_bundle(UiCameraBundle::default());
 
     commands
@@ -75,23 +83,31 @@ fn update_delay_timer(
     player_transform: Query<&Transform, (With<Player>, Without<MainCamera>)>,
     mut state: ResMut<State<AppState>>,
     time: Res<Time>,
+    settings: Res<Settings>,
+    mut sounds: EventWriter<PlaySound>,
     mut text: Query<(&mut Text, &mut Timer, &mut CameraSpeed)>,
 ) {
     let (mut text, mut timer, mut camera_speed) = text.single_mut();
+    let previous_secs = TIMER_SECS - timer.elapsed_secs();
     timer.tick(time.delta());
     if timer.finished() {
+        sounds.send(PlaySound::new(SoundEffect::CountdownEnd));
         state.set(AppState::Game).unwrap();
         return;
     }
     let secs = TIMER_SECS - timer.elapsed_secs();
+    // Tick once for every whole second counted down
+    if secs.ceil() < previous_secs.ceil() {
+        sounds.send(PlaySound::new(SoundEffect::Countdown));
+    }
     text.sections[0].value = format!("{secs:.2}");
 
     const CAMERA_SECS: f32 = (TIMER_SECS) * (2. / 3.);
 
-    // Lerp camera to player position
+    // Lerp camera to player position, or start on the player with reduced motion
     let mut camera_transform = camera.single_mut();
     let player_transform = player_transform.single();
-    if timer.elapsed_secs() < CAMERA_SECS {
+    if timer.elapsed_secs() < CAMERA_SECS && !settings.reduced_motion {
         let cam_speed;
         if let Some(speed) = camera_speed.speed {
             cam_speed = speed;
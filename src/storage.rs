@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, io, path::PathBuf};
+
+// Directory where save files and user level packs are kept
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("beeline"))
+}
+
+// Directory where user preferences are kept
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("beeline"))
+}
+
+// Load a save file from the data directory, falling back to the default value if it does not exist
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    load_from(data_dir(), name)
+}
+
+// Load a file from the config directory, falling back to the default value if it does not exist
+pub fn load_config<T: DeserializeOwned + Default>(name: &str) -> T {
+    load_from(config_dir(), name)
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) -> io::Result<()> {
+    save_to(data_dir(), name, value)
+}
+
+pub fn save_config<T: Serialize>(name: &str, value: &T) -> io::Result<()> {
+    save_to(config_dir(), name, value)
+}
+
+fn load_from<T: DeserializeOwned + Default>(dir: Option<PathBuf>, name: &str) -> T {
+    let path = match dir {
+        Some(dir) => dir.join(name),
+        None => return T::default(),
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|error| {
+            warn!("Failed to parse {}: {error}", path.display());
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+fn save_to<T: Serialize>(dir: Option<PathBuf>, name: &str, value: &T) -> io::Result<()> {
+    let dir =
+        dir.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Could not find directory"))?;
+    // The name may include subdirectories
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap_or(&dir))?;
+
+    let contents = ron::ser::to_string_pretty(value, PrettyConfig::default())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    fs::write(path, contents)
+}
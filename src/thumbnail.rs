@@ -0,0 +1,217 @@
+use crate::{
+    enemy::Projectile,
+    level_pack::LevelPacks,
+    world::{GameWorld, Tile},
+};
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use image::{imageops, imageops::FilterType, Rgba, RgbaImage};
+use std::{f32::consts::PI, fs, io, path::Path};
+
+// Size of a single frame in the game's sprite sheets
+const FRAME_SIZE: u32 = 24;
+
+// Matches the ClearColor used in game
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([51, 51, 102, 255]);
+
+const WALL_TEXTURES: [(&str, &[u8]); 16] = [
+    ("eeee", include_bytes!("../assets/walls/eeee.png")),
+    ("eeew", include_bytes!("../assets/walls/eeew.png")),
+    ("eewe", include_bytes!("../assets/walls/eewe.png")),
+    ("eeww", include_bytes!("../assets/walls/eeww.png")),
+    ("ewee", include_bytes!("../assets/walls/ewee.png")),
+    ("ewew", include_bytes!("../assets/walls/ewew.png")),
+    ("ewwe", include_bytes!("../assets/walls/ewwe.png")),
+    ("ewww", include_bytes!("../assets/walls/ewww.png")),
+    ("weee", include_bytes!("../assets/walls/weee.png")),
+    ("weew", include_bytes!("../assets/walls/weew.png")),
+    ("wewe", include_bytes!("../assets/walls/wewe.png")),
+    ("weww", include_bytes!("../assets/walls/weww.png")),
+    ("wwee", include_bytes!("../assets/walls/wwee.png")),
+    ("wwew", include_bytes!("../assets/walls/wwew.png")),
+    ("wwwe", include_bytes!("../assets/walls/wwwe.png")),
+    ("wwww", include_bytes!("../assets/walls/wwww.png")),
+];
+
+// Tile images used to render thumbnails, decoded and scaled to a fixed tile size. They are
+// embedded in the binary so thumbnails can be rendered without a GPU or the assets directory
+pub struct Tileset {
+    tile_size: u32,
+    walls: Vec<(&'static str, RgbaImage)>,
+    missile_spawner: RgbaImage,
+    laser_spawner: RgbaImage,
+    trap: RgbaImage,
+    goal: RgbaImage,
+    checkpoint: RgbaImage,
+    nectar: RgbaImage,
+    start: RgbaImage,
+}
+
+impl Tileset {
+    pub fn new(tile_size: u32) -> Self {
+        // Decode the first frame of a sprite sheet and scale it to the tile size
+        let load = |bytes: &[u8]| {
+            let sheet = image::load_from_memory(bytes).unwrap().to_rgba8();
+            let frame = imageops::crop_imm(&sheet, 0, 0, FRAME_SIZE, FRAME_SIZE).to_image();
+            imageops::resize(&frame, tile_size, tile_size, FilterType::Triangle)
+        };
+
+        Self {
+            tile_size,
+            walls: WALL_TEXTURES
+                .iter()
+                .map(|(name, bytes)| (*name, load(bytes)))
+                .collect(),
+            missile_spawner: load(include_bytes!("../assets/missile-spawner.png")),
+            laser_spawner: load(include_bytes!("../assets/laser-spawner.png")),
+            trap: load(include_bytes!("../assets/trap.png")),
+            goal: load(include_bytes!("../assets/goal.png")),
+            checkpoint: load(include_bytes!("../assets/checkpoint.png")),
+            nectar: load(include_bytes!("../assets/nectar.png")),
+            start: load(include_bytes!("../assets/bee.png")),
+        }
+    }
+
+    fn wall(&self, name: &str) -> &RgbaImage {
+        &self
+            .walls
+            .iter()
+            .find(|(wall_name, _)| *wall_name == name)
+            .unwrap()
+            .1
+    }
+
+    // Render the layout of the given world into an image
+    pub fn render(&self, world: &GameWorld) -> RgbaImage {
+        let height = world.layout.len() as u32;
+        let width = world.layout.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        let mut thumbnail = RgbaImage::from_pixel(
+            width.max(1) * self.tile_size,
+            height.max(1) * self.tile_size,
+            BACKGROUND_COLOR,
+        );
+
+        for (i, row) in world.layout.iter().enumerate() {
+            for (j, tile) in row.iter().enumerate() {
+                let (x, y) = (j as u32 * self.tile_size, i as u32 * self.tile_size);
+                match tile {
+                    Some(Tile::Wall) => {
+                        let name = world.get_wall_texture_name(j, i);
+                        imageops::overlay(&mut thumbnail, self.wall(name), x, y);
+                    }
+                    Some(Tile::Spawner(spawner)) => match spawner.projectile {
+                        Projectile::Missile => {
+                            imageops::overlay(&mut thumbnail, &self.missile_spawner, x, y);
+                        }
+                        Projectile::Laser { angle } => {
+                            // Only quarter turns are drawn exactly, round to the nearest one
+                            let turns = ((angle - PI / 2.0) / (PI / 2.0)).round() as i32;
+                            let sprite = match turns.rem_euclid(4) {
+                                1 => imageops::rotate270(&self.laser_spawner),
+                                2 => imageops::rotate180(&self.laser_spawner),
+                                3 => imageops::rotate90(&self.laser_spawner),
+                                _ => self.laser_spawner.clone(),
+                            };
+                            imageops::overlay(&mut thumbnail, &sprite, x, y);
+                        }
+                    },
+                    Some(Tile::Trap) => imageops::overlay(&mut thumbnail, &self.trap, x, y),
+                    Some(Tile::Goal { .. }) => imageops::overlay(&mut thumbnail, &self.goal, x, y),
+                    Some(Tile::Checkpoint) => {
+                        imageops::overlay(&mut thumbnail, &self.checkpoint, x, y);
+                    }
+                    Some(Tile::Nectar) => imageops::overlay(&mut thumbnail, &self.nectar, x, y),
+                    None => {}
+                }
+            }
+        }
+
+        let (x, y) = world.player_start_coordinates;
+        imageops::overlay(
+            &mut thumbnail,
+            &self.start,
+            x as u32 * self.tile_size,
+            y as u32 * self.tile_size,
+        );
+
+        thumbnail
+    }
+}
+
+// Convert a rendered thumbnail into an image that bevy can display
+pub fn to_bevy_image(thumbnail: RgbaImage) -> Image {
+    Image::new(
+        Extent3d {
+            width: thumbnail.width(),
+            height: thumbnail.height(),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        thumbnail.into_raw(),
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+// Thumbnails of every level in every pack, None if the level could not be loaded
+pub struct LevelThumbnails(Vec<Vec<Option<Handle<Image>>>>);
+
+impl LevelThumbnails {
+    const TILE_SIZE: u32 = 4;
+
+    pub fn get_handle(&self, pack: usize, level: usize) -> Option<Handle<Image>> {
+        self.0[pack][level].clone()
+    }
+}
+
+impl FromWorld for LevelThumbnails {
+    fn from_world(world: &mut World) -> Self {
+        let tileset = Tileset::new(Self::TILE_SIZE);
+        let world = world.cell();
+        let packs = world.get_resource::<LevelPacks>().unwrap();
+        let mut images = world.get_resource_mut::<Assets<Image>>().unwrap();
+
+        Self(
+            packs
+                .0
+                .iter()
+                .enumerate()
+                .map(|(pack, level_pack)| {
+                    (0..level_pack.levels.len())
+                        .map(|level| {
+                            let game_world = GameWorld::load_level(&packs, pack, level).ok()?;
+                            Some(images.add(to_bevy_image(tileset.render(&game_world))))
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+fn to_file_name(name: &str) -> String {
+    name.to_lowercase().replace(' ', "-")
+}
+
+// Write a PNG thumbnail of every level into the given directory, one subdirectory per pack
+pub fn export_thumbnails(directory: &Path, tile_size: u32) -> io::Result<()> {
+    let tileset = Tileset::new(tile_size);
+    let packs = LevelPacks::load();
+
+    for (pack, level_pack) in packs.0.iter().enumerate() {
+        let pack_directory = directory.join(to_file_name(&level_pack.name));
+        fs::create_dir_all(&pack_directory)?;
+
+        for (level, info) in level_pack.levels.iter().enumerate() {
+            let path = pack_directory.join(format!("{}.png", to_file_name(&info.name)));
+            tileset
+                .render(&GameWorld::load_level(&packs, pack, level)?)
+                .save(&path)
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+            println!("Wrote {}", path.display());
+        }
+    }
+
+    Ok(())
+}
@@ -1,14 +1,22 @@
+pub mod widgets;
+
 use crate::{
+    daily::DailyPlugin,
     game_overlay::GameOverlayPlugin,
     help::HelpPlugin,
+    leaderboard::{LeaderboardLevel, LeaderboardPlugin},
     level_select::LevelSelectPlugin,
     menu::MenuPlugin,
+    minimap::MinimapPlugin,
+    pack_select::PackSelectPlugin,
     retry::RetryPlugin,
-    upgrade_select::{UpgradeButton, UpgradeSelectPlugin},
+    settings::{Settings, SettingsPlugin},
+    upgrade_select::UpgradeSelectPlugin,
     victory::VictoryPlugin,
     AppState,
 };
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use widgets::{Selected, TextInput, WidgetsPlugin};
 
 pub const NORMAL_BUTTON_COLOR: Color = Color::rgb(0.65, 0.8, 0.44);
 pub const ACTIVE_BUTTON_COLOR: Color = Color::rgb(0.98, 0.82, 0.48);
@@ -16,6 +24,11 @@ pub const INACTIVE_BUTTON_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
 
 struct UiPlugin;
 
+// Runs after bevy has updated button interactions from the mouse, so that buttons pressed with
+// the keyboard or a gamepad are not overwritten before the menus see them
+#[derive(StageLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct UiNavigationStage;
+
 #[derive(Clone)]
 pub struct GameFont(Handle<Font>);
 
@@ -35,12 +48,25 @@ impl FromWorld for GameFont {
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameFont>()
+            .init_resource::<FocusedButton>()
+            .add_stage_after(
+                CoreStage::PreUpdate,
+                UiNavigationStage,
+                SystemStage::single(navigate_buttons),
+            )
+            .add_system(focus_hovered_button)
+            .add_system(scale_text)
             .add_system_set(SystemSet::on_update(AppState::Menu).with_system(manage_button_colors))
             .add_system_set(
                 SystemSet::on_update(AppState::UpgradeSelect)
                     .with_system(manage_button_colors)
                     .with_system(manage_back_button),
             )
+            .add_system_set(
+                SystemSet::on_update(AppState::PackSelect)
+                    .with_system(manage_button_colors)
+                    .with_system(manage_back_button),
+            )
             .add_system_set(
                 SystemSet::on_update(AppState::LevelSelect)
                     .with_system(manage_button_colors)
@@ -51,6 +77,26 @@ impl Plugin for UiPlugin {
                     .with_system(manage_button_colors)
                     .with_system(manage_back_button),
             )
+            .add_system_set(
+                SystemSet::on_update(AppState::Settings)
+                    .with_system(manage_button_colors)
+                    .with_system(manage_back_button),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Assist)
+                    .with_system(manage_button_colors)
+                    .with_system(manage_back_button),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Daily)
+                    .with_system(manage_button_colors)
+                    .with_system(manage_back_button),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Leaderboard)
+                    .with_system(manage_button_colors)
+                    .with_system(manage_back_button),
+            )
             .add_system_set(SystemSet::on_update(AppState::Retry).with_system(manage_button_colors))
             .add_system_set(
                 SystemSet::on_update(AppState::Victory).with_system(manage_button_colors),
@@ -63,17 +109,92 @@ pub struct UiPlugins;
 impl PluginGroup for UiPlugins {
     fn build(&mut self, group: &mut PluginGroupBuilder) {
         group
+            .add(PackSelectPlugin)
             .add(LevelSelectPlugin)
             .add(MenuPlugin)
             .add(UpgradeSelectPlugin)
             .add(HelpPlugin)
+            .add(SettingsPlugin)
             .add(RetryPlugin)
             .add(GameOverlayPlugin)
+            .add(MinimapPlugin)
             .add(VictoryPlugin)
+            .add(LeaderboardPlugin)
+            .add(DailyPlugin)
+            .add(WidgetsPlugin)
             .add(UiPlugin);
     }
 }
 
+// Font sizes that text was spawned with, before the text scale setting was applied
+#[derive(Component)]
+struct ScaledText {
+    base_sizes: Vec<f32>,
+    scaled_sizes: Vec<f32>,
+}
+
+fn scale_text(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    settings: Res<Settings>,
+    mut text: Query<(
+        Entity,
+        &mut Text,
+        ChangeTrackers<Text>,
+        Option<&mut ScaledText>,
+    )>,
+) {
+    for (entity, mut text, tracker, scaled) in text.iter_mut() {
+        if !tracker.is_changed() && !settings.is_changed() {
+            continue;
+        }
+
+        let (base_sizes, scaled_sizes): (Vec<f32>, Vec<f32>) = text
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| {
+                let size = section.style.font_size;
+                // A size other than the one set here was set by whoever changed the text
+                let base = match &scaled {
+                    Some(scaled) if scaled.scaled_sizes.get(i) == Some(&size) => {
+                        scaled.base_sizes[i]
+                    }
+                    _ => size,
+                };
+                if section.style.font == font.0 {
+                    (base, base * settings.text_scale)
+                } else {
+                    (base, base)
+                }
+            })
+            .unzip();
+
+        // Only write to the text when a size changed, to avoid triggering change detection
+        let sizes_changed = text
+            .sections
+            .iter()
+            .zip(&scaled_sizes)
+            .any(|(section, size)| section.style.font_size != *size);
+        if sizes_changed {
+            for (section, size) in text.sections.iter_mut().zip(&scaled_sizes) {
+                section.style.font_size = *size;
+            }
+        }
+
+        let scaled_text = ScaledText {
+            base_sizes,
+            scaled_sizes,
+        };
+        match scaled {
+            Some(mut scaled) => *scaled = scaled_text,
+            None => {
+                commands.entity(entity).insert(scaled_text);
+            }
+        }
+    }
+}
+
 #[derive(Component)]
 struct BackButton;
 
@@ -113,28 +234,223 @@ pub fn spawn_back_button(commands: &mut Commands, font: Handle<Font>) {
 
 fn manage_back_button(
     mut state: ResMut<State<AppState>>,
+    leaderboard_level: Option<Res<LeaderboardLevel>>,
     interaction: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
 ) {
     for interaction in interaction.iter() {
         if matches!(interaction, Interaction::Clicked) {
-            // Go back menu state when back button is clicked
-            state.set(AppState::Menu).unwrap();
+            // Go back to the pack browser from level select and to wherever a leaderboard was
+            // opened from, otherwise go back to the menu
+            let previous = match (state.current(), &leaderboard_level) {
+                (AppState::LevelSelect, _) => AppState::PackSelect,
+                (AppState::Leaderboard, Some(level)) => level.previous_state.clone(),
+                _ => AppState::Menu,
+            };
+            state.set(previous).unwrap();
         }
     }
 }
 
 fn manage_button_colors(
+    focused_button: Res<FocusedButton>,
     mut interaction: Query<
-        (&Interaction, &mut UiColor),
-        (Changed<Interaction>, With<Button>, Without<UpgradeButton>),
+        (
+            Entity,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+            Option<(&Selected, ChangeTrackers<Selected>)>,
+            &mut UiColor,
+        ),
+        With<Button>,
     >,
 ) {
-    for (interaction, mut color) in interaction.iter_mut() {
-        *color = if matches!(interaction, Interaction::None) {
-            NORMAL_BUTTON_COLOR
+    for (entity, interaction, tracker, selected, mut color) in interaction.iter_mut() {
+        let selected_changed = selected.map_or(false, |(_, tracker)| tracker.is_changed());
+        if tracker.is_changed() || selected_changed || focused_button.is_changed() {
+            let focused = focused_button.is_focused(entity);
+            *color = if !matches!(interaction, Interaction::None) || focused {
+                ACTIVE_BUTTON_COLOR
+            } else if selected.map_or(false, |(selected, _)| selected.0) {
+                INACTIVE_BUTTON_COLOR
+            } else {
+                NORMAL_BUTTON_COLOR
+            }
+            .into();
+        }
+    }
+}
+
+// Button that is selected for keyboard and gamepad input
+#[derive(Default)]
+pub struct FocusedButton {
+    entity: Option<Entity>,
+    // Button that was pressed with the keyboard or a gamepad, released on the next frame
+    pressed: Option<Entity>,
+}
+
+impl FocusedButton {
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.entity == Some(entity)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Navigation {
+    Direction(Vec2),
+    Next,
+    Previous,
+    Confirm,
+    Back,
+}
+
+impl Navigation {
+    // Space types a space while a text input is focused, so only Enter confirms then
+    fn from_input(
+        keyboard_input: &Input<KeyCode>,
+        gamepad_input: &Input<GamepadButton>,
+        gamepads: &Gamepads,
+        typing: bool,
+    ) -> Option<Self> {
+        let gamepad_pressed = |button_type| {
+            gamepads
+                .iter()
+                .any(|gamepad| gamepad_input.just_pressed(GamepadButton(*gamepad, button_type)))
+        };
+        let shift =
+            keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+
+        if keyboard_input.just_pressed(KeyCode::Up) || gamepad_pressed(GamepadButtonType::DPadUp) {
+            Some(Navigation::Direction(Vec2::Y))
+        } else if keyboard_input.just_pressed(KeyCode::Down)
+            || gamepad_pressed(GamepadButtonType::DPadDown)
+        {
+            Some(Navigation::Direction(-Vec2::Y))
+        } else if keyboard_input.just_pressed(KeyCode::Left)
+            || gamepad_pressed(GamepadButtonType::DPadLeft)
+        {
+            Some(Navigation::Direction(-Vec2::X))
+        } else if keyboard_input.just_pressed(KeyCode::Right)
+            || gamepad_pressed(GamepadButtonType::DPadRight)
+        {
+            Some(Navigation::Direction(Vec2::X))
+        } else if keyboard_input.just_pressed(KeyCode::Tab) {
+            Some(if shift {
+                Navigation::Previous
+            } else {
+                Navigation::Next
+            })
+        } else if keyboard_input.just_pressed(KeyCode::Return)
+            || (keyboard_input.just_pressed(KeyCode::Space) && !typing)
+            || gamepad_pressed(GamepadButtonType::South)
+        {
+            Some(Navigation::Confirm)
+        } else if keyboard_input.just_pressed(KeyCode::Escape)
+            || gamepad_pressed(GamepadButtonType::East)
+        {
+            Some(Navigation::Back)
         } else {
-            ACTIVE_BUTTON_COLOR
+            None
+        }
+    }
+}
+
+fn navigate_buttons(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut focused_button: ResMut<FocusedButton>,
+    mut buttons: Query<(Entity, &GlobalTransform, &mut Interaction), With<Button>>,
+    back_button: Query<Entity, With<BackButton>>,
+    text_inputs: Query<&TextInput>,
+) {
+    // Release the button pressed last frame
+    if let Some(pressed) = focused_button.pressed.take() {
+        if let Ok((_, _, mut interaction)) = buttons.get_mut(pressed) {
+            *interaction = Interaction::None;
+        }
+    }
+
+    // Forget the focused button once it no longer exists, such as after changing menus
+    if let Some(entity) = focused_button.entity {
+        if buttons.get(entity).is_err() {
+            focused_button.entity = None;
+        }
+    }
+
+    let typing = text_inputs.iter().any(TextInput::is_focused);
+    let navigation =
+        match Navigation::from_input(&keyboard_input, &gamepad_input, &gamepads, typing) {
+            Some(navigation) => navigation,
+            None => return,
+        };
+
+    // Buttons in reading order, from the top left to the bottom right
+    let mut positions: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+    positions.sort_by(|(_, a), (_, b)| {
+        b.y.partial_cmp(&a.y)
+            .unwrap()
+            .then(a.x.partial_cmp(&b.x).unwrap())
+    });
+    let current = focused_button
+        .entity
+        .and_then(|entity| positions.iter().position(|(other, _)| *other == entity));
+
+    let next = match (navigation, current) {
+        (Navigation::Confirm | Navigation::Back, _) => {
+            let pressed = match navigation {
+                Navigation::Confirm => focused_button.entity,
+                _ => back_button.get_single().ok(),
+            };
+            if let Some(pressed) = pressed {
+                if let Ok((_, _, mut interaction)) = buttons.get_mut(pressed) {
+                    *interaction = Interaction::Clicked;
+                    focused_button.pressed = Some(pressed);
+                }
+            }
+            return;
+        }
+        // Focus the first button before moving focus around
+        (_, None) => positions.first().map(|(entity, _)| *entity),
+        (Navigation::Next, Some(current)) => positions
+            .get((current + 1) % positions.len())
+            .map(|(entity, _)| *entity),
+        (Navigation::Previous, Some(current)) => positions
+            .get((current + positions.len() - 1) % positions.len())
+            .map(|(entity, _)| *entity),
+        (Navigation::Direction(direction), Some(current)) => {
+            // Move to the closest button in the given direction, preferring buttons in line with
+            // the current one
+            let origin = positions[current].1;
+            positions
+                .iter()
+                .filter_map(|(entity, position)| {
+                    let offset = *position - origin;
+                    let along = offset.dot(direction);
+                    let across = (offset - direction * along).length();
+                    (along > 0.0).then(|| (*entity, along + across * 2.0))
+                })
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .map(|(entity, _)| entity)
+                .or(focused_button.entity)
+        }
+    };
+
+    if next != focused_button.entity {
+        focused_button.entity = next;
+    }
+}
+
+// Keep the mouse and keyboard in agreement about which button is selected
+fn focus_hovered_button(
+    mut focused_button: ResMut<FocusedButton>,
+    interaction: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+) {
+    for (entity, interaction) in interaction.iter() {
+        if matches!(interaction, Interaction::Hovered) && !focused_button.is_focused(entity) {
+            focused_button.entity = Some(entity);
         }
-        .into();
     }
 }
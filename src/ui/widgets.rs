@@ -0,0 +1,771 @@
+use super::{FocusedButton, GameFont, INACTIVE_BUTTON_COLOR, NORMAL_BUTTON_COLOR};
+use bevy::{
+    ecs::system::EntityCommands,
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::FocusPolicy,
+};
+
+pub const TITLE_FONT_SIZE: f32 = 70.0;
+pub const BUTTON_FONT_SIZE: f32 = 40.0;
+pub const LABEL_FONT_SIZE: f32 = 35.0;
+
+pub const BUTTON_WIDTH: f32 = 300.0;
+pub const BUTTON_HEIGHT: f32 = 65.0;
+const LIST_ITEM_HEIGHT: f32 = 70.0;
+
+// Space between the widgets of a list
+const SPACING: f32 = 8.0;
+
+// Distance scrolled by one line of a mouse wheel, in pixels
+const SCROLL_LINE_HEIGHT: f32 = 40.0;
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(manage_toggles)
+            .add_system(manage_slider_buttons)
+            .add_system(drag_slider_tracks)
+            .add_system(update_sliders)
+            .add_system(scroll_lists)
+            .add_system(type_text)
+            .add_system(show_tooltips);
+    }
+}
+
+fn text_bundle(
+    font: &GameFont,
+    text: impl Into<String>,
+    font_size: f32,
+    color: Color,
+) -> TextBundle {
+    TextBundle {
+        text: Text::with_section(
+            text,
+            TextStyle {
+                font: font.get_handle(),
+                font_size,
+                color,
+            },
+            TextAlignment::default(),
+        ),
+        focus_policy: FocusPolicy::Pass,
+        ..TextBundle::default()
+    }
+}
+
+// Full screen root of a menu, with room for tooltips at the bottom
+pub fn spawn_menu_screen(
+    commands: &mut Commands,
+    font: &GameFont,
+    children: impl FnOnce(&mut ChildBuilder),
+) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::FlexEnd,
+                ..Style::default()
+            },
+            color: Color::NONE.into(),
+            ..NodeBundle::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: Rect {
+                            bottom: Val::Percent(2.0),
+                            ..Rect::default()
+                        },
+                        ..Style::default()
+                    },
+                    ..text_bundle(font, "", 30.0, Color::WHITE)
+                })
+                .insert(TooltipText);
+
+            children(parent);
+        });
+}
+
+// Title at the top of the screen
+pub fn spawn_title(parent: &mut ChildBuilder, font: &GameFont, title: impl Into<String>) {
+    parent.spawn_bundle(TextBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: Rect {
+                top: Val::Percent(2.0),
+                ..Rect::default()
+            },
+            ..Style::default()
+        },
+        ..text_bundle(font, title, TITLE_FONT_SIZE, Color::WHITE)
+    });
+}
+
+// Plain text, sections are given as (text, color) pairs
+pub fn spawn_text<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    font_size: f32,
+    sections: impl IntoIterator<Item = (String, Color)>,
+) -> EntityCommands<'w, 's, 'a> {
+    parent.spawn_bundle(TextBundle {
+        text: Text {
+            sections: sections
+                .into_iter()
+                .map(|(value, color)| TextSection {
+                    value,
+                    style: TextStyle {
+                        font: font.get_handle(),
+                        font_size,
+                        color,
+                    },
+                })
+                .collect(),
+            ..Text::default()
+        },
+        focus_policy: FocusPolicy::Pass,
+        ..TextBundle::default()
+    })
+}
+
+pub fn spawn_label<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    text: impl Into<String>,
+    font_size: f32,
+) -> EntityCommands<'w, 's, 'a> {
+    parent.spawn_bundle(text_bundle(font, text, font_size, Color::WHITE))
+}
+
+fn button_style(size: Size<Val>) -> Style {
+    Style {
+        size,
+        margin: Rect::all(Val::Px(SPACING / 2.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        flex_shrink: 0.0,
+        ..Style::default()
+    }
+}
+
+// Button with custom contents, colored by the ui plugin when hovered or focused
+pub fn spawn_button_with<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    size: Size<Val>,
+    children: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    let mut button = parent.spawn_bundle(ButtonBundle {
+        style: button_style(size),
+        color: NORMAL_BUTTON_COLOR.into(),
+        ..ButtonBundle::default()
+    });
+    button.with_children(children);
+    button
+}
+
+// Buttons that stand for a choice, such as a chosen upgrade, are highlighted while selected
+#[derive(Component)]
+pub struct Selected(pub bool);
+
+impl Selected {
+    // Avoid triggering change detection when nothing changed
+    pub fn set(&mut self, selected: bool) {
+        if self.0 != selected {
+            self.0 = selected;
+        }
+    }
+}
+
+// Looks like a button but can not be focused or clicked
+pub fn spawn_disabled_button_with<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    size: Size<Val>,
+    children: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    let mut button = parent.spawn_bundle(NodeBundle {
+        style: button_style(size),
+        color: INACTIVE_BUTTON_COLOR.into(),
+        ..NodeBundle::default()
+    });
+    button.with_children(children);
+    button
+}
+
+pub fn spawn_button<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    text: impl Into<String>,
+) -> EntityCommands<'w, 's, 'a> {
+    let text = text_bundle(font, text, BUTTON_FONT_SIZE, Color::BLACK);
+    spawn_button_with(
+        parent,
+        Size::new(Val::Px(BUTTON_WIDTH), Val::Px(BUTTON_HEIGHT)),
+        |parent| {
+            parent.spawn_bundle(text);
+        },
+    )
+}
+
+// Column of buttons in the middle of the screen, each tagged with its own component
+pub fn spawn_button_list<T: Component>(
+    parent: &mut ChildBuilder,
+    font: &GameFont,
+    buttons: impl IntoIterator<Item = (T, &'static str)>,
+) {
+    spawn_column(parent, |parent| {
+        for (component, text) in buttons {
+            spawn_button(parent, font, text).insert(component);
+        }
+    });
+}
+
+// Full width button in a scroll list
+pub fn spawn_list_button<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    children: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    spawn_button_with(parent, list_item_size(), children)
+}
+
+pub fn list_item_size() -> Size<Val> {
+    Size::new(Val::Percent(90.0), Val::Px(LIST_ITEM_HEIGHT))
+}
+
+// Centered column that lays out its children from top to bottom
+pub fn spawn_column(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                margin: Rect::all(Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..Style::default()
+            },
+            color: Color::NONE.into(),
+            ..NodeBundle::default()
+        })
+        .with_children(children);
+}
+
+// Row with a label on the left and a control on the right
+pub fn spawn_row<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    label: &str,
+    control: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    let mut row = parent.spawn_bundle(NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0), Val::Px(50.0)),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            flex_shrink: 0.0,
+            ..Style::default()
+        },
+        color: Color::NONE.into(),
+        // Rows are hovered alongside their controls, so tooltips can be shown for the whole row
+        focus_policy: FocusPolicy::Pass,
+        ..NodeBundle::default()
+    });
+    row.insert(Interaction::default()).with_children(|parent| {
+        spawn_label(parent, font, label, LABEL_FONT_SIZE);
+        control(parent);
+    });
+    row
+}
+
+fn control_size() -> Size<Val> {
+    Size::new(Val::Px(220.0), Val::Px(42.0))
+}
+
+// Button in a row that shows the current value of a setting, the caller decides what clicking does
+pub fn spawn_choice<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    value: impl Into<String>,
+) -> EntityCommands<'w, 's, 'a> {
+    let text = text_bundle(font, value, 30.0, Color::BLACK);
+    spawn_button_with(parent, control_size(), |parent| {
+        parent.spawn_bundle(text);
+    })
+}
+
+// On/off switch, flipped whenever it is clicked
+#[derive(Component)]
+pub struct Toggle(pub bool);
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+pub fn spawn_toggle<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    label: &str,
+    value: bool,
+    component: impl Component,
+) -> EntityCommands<'w, 's, 'a> {
+    spawn_row(parent, font, label, |parent| {
+        spawn_choice(parent, font, on_off(value))
+            .insert(Toggle(value))
+            .insert(component);
+    })
+}
+
+fn manage_toggles(
+    mut toggles: Query<(&Interaction, &mut Toggle, &Children), Changed<Interaction>>,
+    mut text: Query<&mut Text>,
+) {
+    for (interaction, mut toggle, children) in toggles.iter_mut() {
+        if matches!(interaction, Interaction::Clicked) {
+            toggle.0 = !toggle.0;
+            if let Ok(mut text) = text.get_mut(children[0]) {
+                text.sections[0].value = on_off(toggle.0).into();
+            }
+        }
+    }
+}
+
+// Value between a minimum and maximum, changed in steps with buttons or by clicking the track
+#[derive(Component)]
+pub struct Slider {
+    pub value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+}
+
+impl Slider {
+    pub fn new(value: f32, min: f32, max: f32, step: f32) -> Self {
+        let mut slider = Self {
+            value,
+            min,
+            max,
+            step,
+        };
+        slider.set_value(value);
+        slider
+    }
+
+    fn set_value(&mut self, value: f32) {
+        let steps = ((value - self.min) / self.step).round();
+        let value = (self.min + steps * self.step).clamp(self.min, self.max);
+        // Avoid triggering change detection when nothing changed
+        if value != self.value {
+            self.value = value;
+        }
+    }
+
+    fn get_fraction(&self) -> f32 {
+        (self.value - self.min) / (self.max - self.min)
+    }
+
+    // Value shown next to the track, as a percentage
+    fn get_text(&self) -> String {
+        format!("{:.0}%", self.value * 100.0)
+    }
+}
+
+#[derive(Component)]
+struct SliderButton {
+    slider: Entity,
+    steps: f32,
+}
+
+#[derive(Component)]
+struct SliderTrack(Entity);
+
+#[derive(Component)]
+struct SliderFill(Entity);
+
+#[derive(Component)]
+struct SliderText(Entity);
+
+fn spawn_step_button(
+    parent: &mut ChildBuilder,
+    font: &GameFont,
+    text: &str,
+    slider: Entity,
+    steps: f32,
+) {
+    let text = text_bundle(font, text, 30.0, Color::BLACK);
+    spawn_button_with(
+        parent,
+        Size::new(Val::Px(36.0), Val::Percent(100.0)),
+        |parent| {
+            parent.spawn_bundle(text);
+        },
+    )
+    .insert(SliderButton { slider, steps });
+}
+
+pub fn spawn_slider<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    label: &str,
+    slider: Slider,
+    component: impl Component,
+) -> EntityCommands<'w, 's, 'a> {
+    let fraction = slider.get_fraction();
+    let value_text = slider.get_text();
+    spawn_row(parent, font, label, |parent| {
+        let mut container = parent.spawn_bundle(NodeBundle {
+            style: Style {
+                size: control_size(),
+                align_items: AlignItems::Center,
+                ..Style::default()
+            },
+            color: Color::NONE.into(),
+            ..NodeBundle::default()
+        });
+        let id = container.id();
+
+        container
+            .insert(slider)
+            .insert(component)
+            .with_children(|parent| {
+                spawn_step_button(parent, font, "<", id, -1.0);
+
+                // The track is not a button, so it is skipped by keyboard navigation
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            flex_grow: 1.0,
+                            size: Size::new(Val::Auto, Val::Percent(100.0)),
+                            margin: Rect::all(Val::Px(SPACING / 2.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Style::default()
+                        },
+                        color: INACTIVE_BUTTON_COLOR.into(),
+                        ..NodeBundle::default()
+                    })
+                    .insert(Interaction::default())
+                    .insert(SliderTrack(id))
+                    .with_children(|parent| {
+                        parent
+                            .spawn_bundle(NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    position: Rect {
+                                        left: Val::Px(0.0),
+                                        bottom: Val::Px(0.0),
+                                        ..Rect::default()
+                                    },
+                                    size: Size::new(
+                                        Val::Percent(fraction * 100.0),
+                                        Val::Percent(100.0),
+                                    ),
+                                    ..Style::default()
+                                },
+                                color: NORMAL_BUTTON_COLOR.into(),
+                                focus_policy: FocusPolicy::Pass,
+                                ..NodeBundle::default()
+                            })
+                            .insert(SliderFill(id));
+                        parent
+                            .spawn_bundle(text_bundle(font, value_text, 26.0, Color::BLACK))
+                            .insert(SliderText(id));
+                    });
+
+                spawn_step_button(parent, font, ">", id, 1.0);
+            });
+    })
+}
+
+fn manage_slider_buttons(
+    buttons: Query<(&Interaction, &SliderButton), Changed<Interaction>>,
+    mut sliders: Query<&mut Slider>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if matches!(interaction, Interaction::Clicked) {
+            if let Ok(mut slider) = sliders.get_mut(button.slider) {
+                let value = slider.value + slider.step * button.steps;
+                slider.set_value(value);
+            }
+        }
+    }
+}
+
+// Set the value from where the track is clicked, following the cursor while it is held down
+fn drag_slider_tracks(
+    windows: Res<Windows>,
+    tracks: Query<(&Interaction, &SliderTrack, &Node, &GlobalTransform)>,
+    mut sliders: Query<&mut Slider>,
+) {
+    let cursor_position = match windows.get_primary().and_then(Window::cursor_position) {
+        Some(cursor_position) => cursor_position,
+        None => return,
+    };
+    for (interaction, track, node, transform) in tracks.iter() {
+        if matches!(interaction, Interaction::Clicked) {
+            if let Ok(mut slider) = sliders.get_mut(track.0) {
+                let left = transform.translation.x - node.size.x / 2.0;
+                let fraction = ((cursor_position.x - left) / node.size.x).clamp(0.0, 1.0);
+                let value = slider.min + fraction * (slider.max - slider.min);
+                slider.set_value(value);
+            }
+        }
+    }
+}
+
+fn update_sliders(
+    sliders: Query<(Entity, &Slider), Changed<Slider>>,
+    mut fills: Query<(&SliderFill, &mut Style)>,
+    mut text: Query<(&SliderText, &mut Text)>,
+) {
+    for (entity, slider) in sliders.iter() {
+        let fraction = slider.get_fraction();
+        for (_, mut style) in fills.iter_mut().filter(|(fill, _)| fill.0 == entity) {
+            style.size.width = Val::Percent(fraction * 100.0);
+        }
+        for (_, mut text) in text.iter_mut().filter(|(text, _)| text.0 == entity) {
+            text.sections[0].value = slider.get_text();
+        }
+    }
+}
+
+// List that only shows the part of its contents that fits, scrolled with the mouse wheel or by
+// moving the focus to a button outside of the view
+#[derive(Component)]
+pub struct ScrollList {
+    offset: f32,
+    content: Entity,
+}
+
+pub fn spawn_scroll_list<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    size: Size<Val>,
+    children: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    let mut list = parent.spawn_bundle(NodeBundle {
+        style: Style {
+            size,
+            margin: Rect::all(Val::Auto),
+            flex_direction: FlexDirection::ColumnReverse,
+            overflow: Overflow::Hidden,
+            ..Style::default()
+        },
+        color: Color::GRAY.into(),
+        ..NodeBundle::default()
+    });
+
+    let mut content = None;
+    list.with_children(|parent| {
+        content = Some(
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        padding: Rect::all(Val::Px(SPACING / 2.0)),
+                        // Let the contents grow past the bottom of the list
+                        flex_shrink: 0.0,
+                        ..Style::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..NodeBundle::default()
+                })
+                .with_children(children)
+                .id(),
+        );
+    });
+    list.insert(ScrollList {
+        offset: 0.0,
+        content: content.unwrap(),
+    });
+    list
+}
+
+fn is_descendant(parents: &Query<&Parent>, mut entity: Entity, ancestor: Entity) -> bool {
+    while let Ok(parent) = parents.get(entity) {
+        if parent.0 == ancestor {
+            return true;
+        }
+        entity = parent.0;
+    }
+    false
+}
+
+fn scroll_lists(
+    windows: Res<Windows>,
+    focused_button: Res<FocusedButton>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut lists: Query<(&mut ScrollList, &Node, &GlobalTransform)>,
+    mut contents: Query<(&Node, &mut Style), Without<ScrollList>>,
+    buttons: Query<(&Node, &GlobalTransform), Without<ScrollList>>,
+    parents: Query<&Parent>,
+) {
+    let scrolled: f32 = mouse_wheel
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        })
+        .sum();
+    let cursor_position = windows.get_primary().and_then(Window::cursor_position);
+
+    for (mut list, node, transform) in lists.iter_mut() {
+        let (content_node, mut content_style) = match contents.get_mut(list.content) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let center = transform.translation.truncate();
+        let half_size = node.size / 2.0;
+        let mut offset = list.offset;
+
+        // Scroll the list under the cursor, scrolling up moves the contents down
+        let hovered = cursor_position.map_or(false, |cursor_position| {
+            let distance = (cursor_position - center).abs();
+            distance.x <= half_size.x && distance.y <= half_size.y
+        });
+        if hovered {
+            offset -= scrolled;
+        }
+
+        // Bring the focused button into view
+        if focused_button.is_changed() {
+            if let Some(focused) = focused_button.entity {
+                if is_descendant(&parents, focused, list.content) {
+                    if let Ok((button_node, button_transform)) = buttons.get(focused) {
+                        let button_top = button_transform.translation.y + button_node.size.y / 2.0;
+                        let button_bottom = button_top - button_node.size.y;
+                        let (top, bottom) = (center.y + half_size.y, center.y - half_size.y);
+                        if button_top > top {
+                            offset -= button_top - top;
+                        } else if button_bottom < bottom {
+                            offset += bottom - button_bottom;
+                        }
+                    }
+                }
+            }
+        }
+
+        let max_offset = (content_node.size.y - node.size.y).max(0.0);
+        offset = offset.clamp(0.0, max_offset);
+        if offset != list.offset {
+            list.offset = offset;
+            content_style.position.top = Val::Px(-offset);
+        }
+    }
+}
+
+// Text field that receives whatever is typed while it is focused. It is focused by clicking it
+// or pressing /, and unfocused by pressing Enter
+#[derive(Component)]
+pub struct TextInput {
+    pub value: String,
+    placeholder: String,
+    focused: bool,
+}
+
+impl TextInput {
+    pub fn clear(&mut self) {
+        self.value.clear();
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn get_text(&self) -> (&str, Color) {
+        if self.value.is_empty() {
+            (&self.placeholder, Color::GRAY)
+        } else {
+            (&self.value, Color::WHITE)
+        }
+    }
+}
+
+pub fn spawn_text_input<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    font: &GameFont,
+    placeholder: &str,
+    focused: bool,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut input = parent.spawn_bundle(text_bundle(font, placeholder, 30.0, Color::GRAY));
+    input.insert(Interaction::default()).insert(TextInput {
+        value: String::new(),
+        placeholder: placeholder.into(),
+        focused,
+    });
+    input
+}
+
+fn type_text(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut inputs: Query<(&mut TextInput, &mut Text, &Interaction)>,
+) {
+    // Tab and Enter are used by menu navigation and, like Backspace, come through as control
+    // characters, which are never typed
+    let typed: String = characters
+        .iter()
+        .map(|event| event.char)
+        .filter(|char| !char.is_control())
+        .collect();
+    let erased = keyboard_input.just_pressed(KeyCode::Back);
+    let focus = keyboard_input.just_pressed(KeyCode::Slash);
+    let unfocus = keyboard_input.just_pressed(KeyCode::Return);
+
+    for (mut input, mut text, interaction) in inputs.iter_mut() {
+        if !input.focused {
+            // The / or click that focuses the input is not typed into it
+            input.focused = focus || matches!(interaction, Interaction::Clicked);
+        } else if unfocus {
+            input.focused = false;
+        } else {
+            if !typed.is_empty() {
+                input.value += &typed;
+            }
+            if erased && !input.value.is_empty() {
+                input.value.pop();
+            }
+        }
+        let (value, color) = input.get_text();
+        if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+// Text shown at the bottom of the screen while the entity or one of its buttons is hovered or
+// focused
+#[derive(Component)]
+pub struct Tooltip(pub String);
+
+#[derive(Component)]
+struct TooltipText;
+
+fn show_tooltips(
+    focused_button: Res<FocusedButton>,
+    tooltips: Query<(Entity, &Interaction, &Tooltip)>,
+    parents: Query<&Parent>,
+    mut text: Query<&mut Text, With<TooltipText>>,
+) {
+    let tooltip = tooltips
+        .iter()
+        .find(|(entity, interaction, _)| {
+            matches!(interaction, Interaction::Hovered)
+                || focused_button.entity.map_or(false, |focused| {
+                    focused == *entity || is_descendant(&parents, focused, *entity)
+                })
+        })
+        .map_or("", |(_, _, tooltip)| tooltip.0.as_str());
+
+    for mut text in text.iter_mut() {
+        if text.sections[0].value != tooltip {
+            text.sections[0].value = tooltip.to_string();
+        }
+    }
+}
@@ -1,200 +1,366 @@
 use crate::{
+    loadout::LoadoutPresets,
     ui::{
-        spawn_back_button, GameFont, ACTIVE_BUTTON_COLOR, INACTIVE_BUTTON_COLOR,
-        NORMAL_BUTTON_COLOR,
+        spawn_back_button,
+        widgets::{
+            spawn_button, spawn_button_with, spawn_column, spawn_label, spawn_menu_screen,
+            spawn_scroll_list, spawn_text, spawn_text_input, spawn_title, Selected, TextInput,
+            Tooltip,
+        },
+        GameFont,
     },
-    upgrades::{create_upgrades_overlay, Upgrade, UpgradeTracker},
+    upgrades::{Upgrade, UpgradeSlot, UpgradeTracker},
     AppState,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::FocusPolicy};
 use strum::IntoEnumIterator;
 
 pub struct UpgradeSelectPlugin;
 
 impl Plugin for UpgradeSelectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(
-            SystemSet::on_enter(AppState::UpgradeSelect).with_system(create_upgrade_select),
-        )
-        .add_system_set(
-            SystemSet::on_update(AppState::UpgradeSelect)
-                .with_system(manage_upgrade_buttons)
-                .with_system(update_upgrades_selected_indicator),
-        );
+        app.init_resource::<LoadoutPresets>()
+            .init_resource::<SelectedSlot>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::UpgradeSelect).with_system(create_upgrade_select),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::UpgradeSelect)
+                    .with_system(manage_upgrade_buttons)
+                    .with_system(manage_slot_buttons)
+                    .with_system(manage_loadout_buttons)
+                    .with_system(update_loadout)
+                    .with_system(update_preset_list)
+                    .with_system(update_upgrades_selected_indicator),
+            );
     }
 }
 
 #[derive(Component)]
-pub struct UpgradeButton(Upgrade);
+struct UpgradeButton(Upgrade);
+
+// Slot that clicked upgrades are put into
+struct SelectedSlot(UpgradeSlot);
+
+impl Default for SelectedSlot {
+    fn default() -> Self {
+        Self(UpgradeSlot::Primary)
+    }
+}
+
+#[derive(Component)]
+struct SlotButton(UpgradeSlot);
+
+// Shows whether an upgrade is active or passive and which slot it is in
+#[derive(Component)]
+struct UpgradeSlotLabel(Upgrade);
+
+#[derive(Component)]
+enum LoadoutButton {
+    Swap,
+    SavePreset,
+    LoadPreset(usize),
+    DeletePreset(usize),
+}
+
+#[derive(Component)]
+struct PresetName;
+
+// Node that holds the preset list, the list is rebuilt whenever the presets change
+#[derive(Component)]
+struct PresetList;
 
 // Component to display how many upgrades are currently selected
 #[derive(Component)]
 struct UpgradesSelectedIndicator;
 
-fn create_upgrade_select(mut commands: Commands, font: Res<GameFont>) {
+fn get_slot_button_text(upgrades: &UpgradeTracker, slot: UpgradeSlot) -> String {
+    let upgrade = upgrades
+        .get_upgrade(slot)
+        .map_or_else(|| "Empty".to_string(), |upgrade| upgrade.to_string());
+    format!("{}: {upgrade}", slot.get_binding())
+}
+
+fn get_slot_text(upgrades: &UpgradeTracker, upgrade: Upgrade) -> String {
+    upgrades
+        .get_slot(upgrade)
+        .map_or_else(String::new, |slot| format!("   {}", slot.get_binding()))
+}
+
+fn create_upgrade_select(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    asset_server: Res<AssetServer>,
+    upgrades: Res<UpgradeTracker>,
+    selected_slot: Res<SelectedSlot>,
+    presets: Res<LoadoutPresets>,
+) {
     commands.spawn_bundle(UiCameraBundle::default());
 
     spawn_back_button(&mut commands, font.get_handle());
-    create_upgrades_overlay(&mut commands, &font);
 
-    commands
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Upgrade Select");
+
+        spawn_label(parent, &font, "", 50.0)
+            .insert(Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Percent(2.0),
+                    right: Val::Percent(2.0),
+                    ..Rect::default()
+                },
+                ..Style::default()
+            })
+            .insert(UpgradesSelectedIndicator);
+
+        spawn_column(parent, |parent| {
+            // Pick a slot, then the upgrade to put into it
+            spawn_row_node(parent, |parent| {
+                for slot in [UpgradeSlot::Primary, UpgradeSlot::Secondary] {
+                    spawn_button(parent, &font, get_slot_button_text(&upgrades, slot))
+                        .insert(SlotButton(slot))
+                        .insert(Selected(slot == selected_slot.0));
+                }
+            });
+
+            // Spawn upgrades in rows of two
+            let upgrades_list: Vec<Upgrade> = Upgrade::iter().collect();
+            for row in upgrades_list.chunks(2) {
+                spawn_row_node(parent, |parent| {
+                    for upgrade in row {
+                        spawn_upgrade_button(parent, &font, &asset_server, &upgrades, *upgrade);
+                    }
+                });
+            }
+
+            spawn_button(parent, &font, "Swap Slots").insert(LoadoutButton::Swap);
+
+            spawn_row_node(parent, |parent| {
+                spawn_text_input(parent, &font, "Type a preset name", true).insert(PresetName);
+                spawn_button(parent, &font, "Save Preset").insert(LoadoutButton::SavePreset);
+            });
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: Color::NONE.into(),
+                    ..NodeBundle::default()
+                })
+                .insert(PresetList)
+                .with_children(|parent| spawn_presets(parent, &font, &presets));
+        });
+    });
+}
+
+fn spawn_row_node(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
         .spawn_bundle(NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
+                align_items: AlignItems::Center,
                 ..Style::default()
             },
             color: Color::NONE.into(),
             ..NodeBundle::default()
         })
-        .with_children(|parent| {
-            const UPGRADE_SELECT_HEIGHT: f32 = 50.0;
-            // Spawn upgrade select title
-            parent.spawn_bundle(TextBundle {
+        .with_children(children);
+}
+
+fn spawn_upgrade_button(
+    parent: &mut ChildBuilder,
+    font: &GameFont,
+    asset_server: &AssetServer,
+    upgrades: &UpgradeTracker,
+    upgrade: Upgrade,
+) {
+    let (badge, badge_color) = if upgrade.is_active() {
+        ("ACTIVE", Color::rgb(0.6, 0.1, 0.1))
+    } else {
+        ("PASSIVE", Color::rgb(0.1, 0.2, 0.5))
+    };
+
+    spawn_button_with(parent, Size::new(Val::Px(320.0), Val::Px(80.0)), |parent| {
+        parent.spawn_bundle(ImageBundle {
+            style: Style {
+                size: Size::new(Val::Px(48.0), Val::Px(48.0)),
+                margin: Rect {
+                    right: Val::Px(12.0),
+                    ..Rect::default()
+                },
+                ..Style::default()
+            },
+            image: asset_server.load(upgrade.get_icon_path()).into(),
+            focus_policy: FocusPolicy::Pass,
+            ..ImageBundle::default()
+        });
+        parent
+            .spawn_bundle(NodeBundle {
                 style: Style {
-                    position_type: PositionType::Absolute,
-                    position: Rect {
-                        top: Val::Percent(2.0),
-                        ..Rect::default()
-                    },
+                    flex_direction: FlexDirection::ColumnReverse,
+                    align_items: AlignItems::FlexStart,
                     ..Style::default()
                 },
-                text: Text::with_section(
-                    "Upgrade Select",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 70.0,
-                        ..TextStyle::default()
-                    },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
+                color: Color::NONE.into(),
+                focus_policy: FocusPolicy::Pass,
+                ..NodeBundle::default()
+            })
+            .with_children(|parent| {
+                spawn_text(parent, font, 30.0, [(upgrade.to_string(), Color::BLACK)]);
+                spawn_text(
+                    parent,
+                    font,
+                    20.0,
+                    [
+                        (badge.to_string(), badge_color),
+                        (get_slot_text(upgrades, upgrade), Color::BLACK),
+                    ],
+                )
+                .insert(UpgradeSlotLabel(upgrade));
             });
+    })
+    .insert(UpgradeButton(upgrade))
+    .insert(Selected(upgrades.has_upgrade(upgrade)))
+    .insert(Tooltip(upgrade.get_description().into()));
+}
 
-            parent
-                .spawn_bundle(TextBundle {
-                    style: Style {
-                        position_type: PositionType::Absolute,
-                        position: Rect {
-                            bottom: Val::Percent(2.0),
-                            right: Val::Percent(2.0),
-                            ..Rect::default()
-                        },
-                        ..Style::default()
-                    },
-                    text: Text::with_section(
-                        "",
-                        TextStyle {
-                            font: font.get_handle(),
-                            font_size: 50.0,
-                            ..TextStyle::default()
-                        },
-                        TextAlignment::default(),
-                    ),
-                    ..TextBundle::default()
-                })
-                .insert(UpgradesSelectedIndicator);
+fn spawn_presets(parent: &mut ChildBuilder, font: &GameFont, presets: &LoadoutPresets) {
+    if presets.0.is_empty() {
+        return;
+    }
+    spawn_scroll_list(
+        parent,
+        Size::new(Val::Px(640.0), Val::Px(160.0)),
+        |parent| {
+            for (index, preset) in presets.0.iter().enumerate() {
+                let upgrade_name = |upgrade: Option<Upgrade>| {
+                    upgrade.map_or_else(|| "Empty".to_string(), |upgrade| upgrade.to_string())
+                };
+                let text = format!(
+                    "{}   {} / {}",
+                    preset.name,
+                    upgrade_name(preset.primary),
+                    upgrade_name(preset.secondary)
+                );
 
-            // Spawn level selector
-            parent
-                .spawn_bundle(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(60.0), Val::Percent(UPGRADE_SELECT_HEIGHT)),
-                        margin: Rect::all(Val::Auto),
-                        flex_direction: FlexDirection::ColumnReverse,
-                        ..Style::default()
-                    },
-                    color: Color::GRAY.into(),
-                    ..NodeBundle::default()
-                })
-                .with_children(|parent| {
-                    let upgrades: Vec<Upgrade> = Upgrade::iter().collect();
-                    for i in (0..upgrades.len()).step_by(3) {
-                        parent
-                            .spawn_bundle(NodeBundle {
-                                style: Style {
-                                    size: Size::new(Val::Auto, Val::Percent(30.0)),
-                                    margin: Rect {
-                                        top: Val::Px(5.0),
-                                        bottom: Val::Px(5.0),
-                                        ..Rect::default()
-                                    },
-                                    ..Style::default()
-                                },
-                                color: Color::GRAY.into(),
-                                ..NodeBundle::default()
-                            })
-                            .with_children(|parent| {
-                                for j in 0..3 {
-                                    if i + j < upgrades.len() {
-                                        let upgrade = upgrades[i + j];
-
-                                        parent
-                                            .spawn_bundle(ButtonBundle {
-                                                style: Style {
-                                                    size: Size::new(
-                                                        Val::Percent(30.0),
-                                                        Val::Percent(100.0),
-                                                    ),
-                                                    margin: Rect::all(Val::Auto),
-                                                    justify_content: JustifyContent::Center,
-                                                    align_items: AlignItems::Center,
-                                                    ..Style::default()
-                                                },
-                                                ..ButtonBundle::default()
-                                            })
-                                            .insert(UpgradeButton(upgrade))
-                                            .with_children(|parent| {
-                                                parent.spawn_bundle(TextBundle {
-                                                    text: Text::with_section(
-                                                        upgrade.to_string(),
-                                                        TextStyle {
-                                                            font: font.get_handle(),
-                                                            font_size: 30.0,
-                                                            color: Color::BLACK,
-                                                        },
-                                                        TextAlignment::default(),
-                                                    ),
-                                                    ..TextBundle::default()
-                                                });
-                                            });
-                                    } else {
-                                        break;
-                                    }
-                                }
-                            });
-                    }
+                spawn_row_node(parent, |parent| {
+                    spawn_button_with(parent, Size::new(Val::Px(460.0), Val::Px(50.0)), |parent| {
+                        spawn_text(parent, font, 26.0, [(text, Color::BLACK)]);
+                    })
+                    .insert(LoadoutButton::LoadPreset(index));
+                    spawn_button_with(parent, Size::new(Val::Px(120.0), Val::Px(50.0)), |parent| {
+                        spawn_text(parent, font, 26.0, [("Delete".to_string(), Color::BLACK)]);
+                    })
+                    .insert(LoadoutButton::DeletePreset(index));
                 });
-        });
+            }
+        },
+    );
 }
 
 fn manage_upgrade_buttons(
     mut upgrades: ResMut<UpgradeTracker>,
-    mut interaction: Query<
-        (&Interaction, &mut UiColor, &UpgradeButton),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut selected_slot: ResMut<SelectedSlot>,
+    interaction: Query<(&Interaction, &UpgradeButton), (Changed<Interaction>, With<Button>)>,
 ) {
-    for (interaction, mut color, button) in interaction.iter_mut() {
-        let has_upgrade = upgrades.has_upgrade(button.0);
-
-        *color = if has_upgrade {
-            INACTIVE_BUTTON_COLOR
-        } else if matches!(interaction, Interaction::None) {
-            NORMAL_BUTTON_COLOR
+    for (interaction, button) in interaction.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        let slot = selected_slot.0;
+        // Clicking the upgrade that is already in the slot empties it
+        if upgrades.get_upgrade(slot) == Some(button.0) {
+            upgrades.clear_slot(slot);
         } else {
-            ACTIVE_BUTTON_COLOR
+            upgrades.set_upgrade(slot, button.0);
+            // Move on to the other slot while it is empty, so a loadout can be picked in two clicks
+            let other_slot = slot.get_other();
+            if upgrades.get_upgrade(other_slot).is_none() {
+                selected_slot.0 = other_slot;
+            }
         }
-        .into();
+    }
+}
 
+fn manage_slot_buttons(
+    mut selected_slot: ResMut<SelectedSlot>,
+    interaction: Query<(&Interaction, &SlotButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in interaction.iter() {
         if matches!(interaction, Interaction::Clicked) {
-            if has_upgrade {
-                upgrades.unset_upgrade(button.0);
-            } else {
-                upgrades.set_upgrade(button.0);
+            selected_slot.0 = button.0;
+        }
+    }
+}
+
+fn manage_loadout_buttons(
+    mut upgrades: ResMut<UpgradeTracker>,
+    mut presets: ResMut<LoadoutPresets>,
+    mut preset_name: Query<&mut TextInput, With<PresetName>>,
+    interaction: Query<(&Interaction, &LoadoutButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in interaction.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        match button {
+            LoadoutButton::Swap => upgrades.swap_slots(),
+            LoadoutButton::SavePreset => {
+                if let Ok(mut preset_name) = preset_name.get_single_mut() {
+                    let name = if preset_name.value.is_empty() {
+                        presets.get_default_name()
+                    } else {
+                        preset_name.value.clone()
+                    };
+                    presets.add(name, &upgrades);
+                    preset_name.clear();
+                }
             }
+            LoadoutButton::LoadPreset(index) => presets.apply(*index, &mut upgrades),
+            LoadoutButton::DeletePreset(index) => presets.remove(*index),
+        }
+    }
+}
+
+fn update_loadout(
+    upgrades: Res<UpgradeTracker>,
+    selected_slot: Res<SelectedSlot>,
+    mut upgrade_buttons: Query<(&mut Selected, &UpgradeButton), Without<SlotButton>>,
+    mut slot_buttons: Query<(&mut Selected, &SlotButton, &Children), Without<UpgradeButton>>,
+    mut labels: Query<(&mut Text, &UpgradeSlotLabel)>,
+    mut text: Query<&mut Text, Without<UpgradeSlotLabel>>,
+) {
+    if !upgrades.is_changed() && !selected_slot.is_changed() {
+        return;
+    }
+    for (mut selected, button) in upgrade_buttons.iter_mut() {
+        selected.set(upgrades.has_upgrade(button.0));
+    }
+    for (mut selected, button, children) in slot_buttons.iter_mut() {
+        selected.set(button.0 == selected_slot.0);
+        if let Ok(mut text) = text.get_mut(children[0]) {
+            text.sections[0].value = get_slot_button_text(&upgrades, button.0);
+        }
+    }
+    for (mut text, label) in labels.iter_mut() {
+        text.sections[1].value = get_slot_text(&upgrades, label.0);
+    }
+}
+
+fn update_preset_list(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    presets: Res<LoadoutPresets>,
+    list: Query<(Entity, Option<&Children>), With<PresetList>>,
+) {
+    if !presets.is_changed() {
+        return;
+    }
+    for (entity, children) in list.iter() {
+        for child in children.iter().flat_map(|children| children.iter()) {
+            commands.entity(*child).despawn_recursive();
         }
+        commands
+            .entity(entity)
+            .with_children(|parent| spawn_presets(parent, &font, &presets));
     }
 }
 
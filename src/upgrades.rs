@@ -1,18 +1,31 @@
-use crate::{ui::GameFont, AppState};
+use crate::{
+    level_pack::LevelPacks,
+    storage,
+    ui::GameFont,
+    world::{GameWorld, WorldType, SPAWN_WORLD},
+    AppState,
+};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::string::ToString;
 use strum_macros::{Display, EnumIter};
 
 const NO_UPGRADE_TEXT: &str = "EMPTY";
 
+const LOADOUT_FILE: &str = "loadout.ron";
+
 pub struct UpgradesPlugin;
 
 impl Plugin for UpgradesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UpgradeTracker>()
+            .add_system_set(SystemSet::on_exit(AppState::UpgradeSelect).with_system(save_loadout))
             .add_system_set(
-                SystemSet::on_update(AppState::UpgradeSelect).with_system(update_upgrades_overlay),
+                // The loadout must be settled before the bee is spawned with it
+                SystemSet::on_enter(AppState::StartDelay)
+                    .with_system(restrict_loadout.before(SPAWN_WORLD)),
             )
+            .add_system_set(SystemSet::on_enter(AppState::Menu).with_system(restore_loadout))
             .add_system_set(
                 SystemSet::on_update(AppState::Game).with_system(update_upgrades_overlay),
             );
@@ -89,75 +102,238 @@ pub fn create_upgrades_overlay(commands: &mut Commands, font: &Res<GameFont>) {
 
 fn update_upgrades_overlay(
     upgrades: Res<UpgradeTracker>,
-    mut overlay_texts: Query<&mut Text, With<UpgradeOverlay>>,
+    saved: Option<Res<SavedLoadout>>,
+    mut overlay_texts: Query<(&mut Text, &UpgradeOverlay)>,
 ) {
-    let mut overlay_texts: Vec<Mut<'_, Text>> = overlay_texts.iter_mut().collect();
-    assert_eq!(overlay_texts.len(), 2);
-
-    overlay_texts[0].sections[0].value = if let Some(upgrade) = upgrades.primary {
-        upgrade.to_string()
-    } else {
-        NO_UPGRADE_TEXT.into()
-    };
-
-    overlay_texts[1].sections[0].value = if let Some(upgrade) = upgrades.secondary {
-        upgrade.to_string()
-    } else {
-        NO_UPGRADE_TEXT.into()
-    };
+    for (mut text, overlay) in overlay_texts.iter_mut() {
+        // Show which input uses the upgrade in each slot
+        let saved_upgrade = saved
+            .as_ref()
+            .and_then(|saved| saved.0.get_upgrade(overlay.0));
+        let upgrade = match (upgrades.get_upgrade(overlay.0), saved_upgrade) {
+            (Some(upgrade), _) => upgrade.to_string(),
+            (None, Some(removed)) => format!("{removed} not allowed on this level"),
+            (None, None) => NO_UPGRADE_TEXT.into(),
+        };
+        text.sections[0].value = format!("{}: {upgrade}", overlay.0.get_binding());
+    }
 }
 
-#[derive(Debug, Display, EnumIter, PartialEq, Copy, Clone)]
+#[derive(Debug, Display, EnumIter, Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub enum Upgrade {
+    #[strum(to_string = "Double Speed")]
     DoubleSpeed,
     Shrink,
     Teleport,
+    #[strum(to_string = "Slow Enemies")]
     SlowEnemies,
 }
 
+impl Upgrade {
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            Upgrade::DoubleSpeed => "Fly twice as fast",
+            Upgrade::Shrink => "Halve your size to fit through tighter gaps",
+            Upgrade::Teleport => "Teleport to the cursor",
+            Upgrade::SlowEnemies => "Lasers and missiles move at half speed",
+        }
+    }
+
+    // Active upgrades are used with the input of their slot, passive upgrades always apply
+    pub fn is_active(&self) -> bool {
+        matches!(self, Upgrade::Teleport)
+    }
+
+    pub fn get_icon_path(&self) -> &'static str {
+        match self {
+            Upgrade::DoubleSpeed => "upgrades/double-speed.png",
+            Upgrade::Shrink => "upgrades/shrink.png",
+            Upgrade::Teleport => "upgrades/teleport.png",
+            Upgrade::SlowEnemies => "upgrades/slow-enemies.png",
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone)]
 pub enum UpgradeSlot {
     Primary,
     Secondary,
 }
 
-#[derive(Debug, Default)]
+impl UpgradeSlot {
+    // Mouse button and key that activate the upgrade in the slot
+    pub fn get_binding(&self) -> &'static str {
+        match self {
+            UpgradeSlot::Primary => "LMB / Q",
+            UpgradeSlot::Secondary => "RMB / E",
+        }
+    }
+
+    pub fn get_other(&self) -> Self {
+        match self {
+            UpgradeSlot::Primary => UpgradeSlot::Secondary,
+            UpgradeSlot::Secondary => UpgradeSlot::Primary,
+        }
+    }
+}
+
+// The loadout, kept between sessions
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Copy, Clone)]
+#[serde(default)]
 pub struct UpgradeTracker {
     pub primary: Option<Upgrade>,
     pub secondary: Option<Upgrade>,
 }
 
 impl UpgradeTracker {
-    pub fn set_upgrade(&mut self, upgrade: Upgrade) {
-        if self.primary == None {
-            self.primary = Some(upgrade);
-        } else if self.secondary == None {
-            self.secondary = Some(upgrade);
+    fn get_slot_mut(&mut self, slot: UpgradeSlot) -> &mut Option<Upgrade> {
+        match slot {
+            UpgradeSlot::Primary => &mut self.primary,
+            UpgradeSlot::Secondary => &mut self.secondary,
         }
     }
 
-    pub fn unset_upgrade(&mut self, upgrade: Upgrade) {
-        if self.primary == Some(upgrade) {
-            self.primary = None;
-        } else if self.secondary == Some(upgrade) {
+    // An upgrade can only be in one slot, so it is moved out of the other slot if it is there
+    pub fn set_upgrade(&mut self, slot: UpgradeSlot, upgrade: Upgrade) {
+        if let Some(current_slot) = self.get_slot(upgrade) {
+            *self.get_slot_mut(current_slot) = None;
+        }
+        *self.get_slot_mut(slot) = Some(upgrade);
+    }
+
+    pub fn clear_slot(&mut self, slot: UpgradeSlot) {
+        *self.get_slot_mut(slot) = None;
+    }
+
+    // Both slots are bound to different inputs, so an active upgrade in both slots would be
+    // triggered twice
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.primary, self.secondary) {
+            (Some(primary), Some(secondary)) if primary == secondary => {
+                Err(format!("{primary} is in both upgrade slots"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Fix loadouts that were edited by hand or saved by an older version
+    fn into_valid(mut self) -> Self {
+        if let Err(error) = self.validate() {
+            warn!("Invalid loadout: {error}");
             self.secondary = None;
         }
+        self
     }
 
+    // The primary slot is activated with the left mouse button, Q or a gamepad's left bumper, the
+    // secondary slot with the right mouse button, E or the right bumper
     pub fn was_upgrade_activated(
         &self,
-        keyboard_input: Res<Input<KeyCode>>,
-        button_input: Res<Input<MouseButton>>,
+        keyboard_input: &Input<KeyCode>,
+        button_input: &Input<MouseButton>,
+        gamepad_input: &Input<GamepadButton>,
+        gamepads: &Gamepads,
         upgrade: Upgrade,
     ) -> bool {
+        let gamepad_pressed = |button_type| {
+            gamepads
+                .iter()
+                .any(|gamepad| gamepad_input.just_pressed(GamepadButton(*gamepad, button_type)))
+        };
         (self.primary == Some(upgrade)
             && (button_input.just_pressed(MouseButton::Left)
-                || keyboard_input.just_pressed(KeyCode::Q)))
+                || keyboard_input.just_pressed(KeyCode::Q)
+                || gamepad_pressed(GamepadButtonType::LeftTrigger)))
             || (self.secondary == Some(upgrade)
                 && (button_input.just_pressed(MouseButton::Right)
-                    || keyboard_input.just_pressed(KeyCode::E)))
+                    || keyboard_input.just_pressed(KeyCode::E)
+                    || gamepad_pressed(GamepadButtonType::RightTrigger)))
     }
 
     pub fn has_upgrade(&self, upgrade: Upgrade) -> bool {
         self.primary == Some(upgrade) || self.secondary == Some(upgrade)
     }
+
+    pub fn get_upgrade(&self, slot: UpgradeSlot) -> Option<Upgrade> {
+        match slot {
+            UpgradeSlot::Primary => self.primary,
+            UpgradeSlot::Secondary => self.secondary,
+        }
+    }
+
+    pub fn get_slot(&self, upgrade: Upgrade) -> Option<UpgradeSlot> {
+        if self.primary == Some(upgrade) {
+            Some(UpgradeSlot::Primary)
+        } else if self.secondary == Some(upgrade) {
+            Some(UpgradeSlot::Secondary)
+        } else {
+            None
+        }
+    }
+
+    // Loadout with the upgrades that aren't in the given list taken out of their slots
+    fn restrict(&self, allowed: &[Upgrade]) -> Self {
+        let is_allowed = |upgrade: &Upgrade| allowed.contains(upgrade);
+        Self {
+            primary: self.primary.filter(is_allowed),
+            secondary: self.secondary.filter(is_allowed),
+        }
+    }
+
+    pub fn swap_slots(&mut self) {
+        std::mem::swap(&mut self.primary, &mut self.secondary);
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = storage::save(LOADOUT_FILE, self) {
+            warn!("Failed to save loadout: {error}");
+        }
+    }
+}
+
+impl FromWorld for UpgradeTracker {
+    fn from_world(_world: &mut World) -> Self {
+        storage::load::<UpgradeTracker>(LOADOUT_FILE).into_valid()
+    }
+}
+
+fn save_loadout(upgrades: Res<UpgradeTracker>) {
+    upgrades.save();
+}
+
+// Loadout the player chose themselves while playing with another one, given back in the menu
+pub struct SavedLoadout(pub UpgradeTracker);
+
+fn restore_loadout(
+    mut commands: Commands,
+    saved: Option<Res<SavedLoadout>>,
+    mut upgrades: ResMut<UpgradeTracker>,
+) {
+    if let Some(saved) = saved {
+        *upgrades = saved.0;
+        commands.remove_resource::<SavedLoadout>();
+    }
+}
+
+// Take upgrades that the level doesn't allow out of the loadout, or put back the ones taken out
+// for an earlier level
+fn restrict_loadout(
+    mut commands: Commands,
+    world: Res<GameWorld>,
+    packs: Res<LevelPacks>,
+    saved: Option<Res<SavedLoadout>>,
+    mut upgrades: ResMut<UpgradeTracker>,
+) {
+    let (pack, index) = match world.world_type {
+        WorldType::Level { pack, index } => (pack, index),
+        _ => return,
+    };
+    let own = saved.map_or(*upgrades, |saved| saved.0);
+    *upgrades = match &packs.get_level(pack, index).allowed_upgrades {
+        Some(allowed) => own.restrict(allowed),
+        None => own,
+    };
+    if *upgrades != own {
+        commands.insert_resource(SavedLoadout(own));
+    }
 }
@@ -1,11 +1,42 @@
 use benimator::{AnimationMode, Play, SpriteSheetAnimation};
 use bevy::prelude::*;
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 pub fn polar_to_cartesian(angle: f32, length: f32) -> Vec2 {
     Vec2::new(length * angle.cos(), length * angle.sin())
 }
 
+// Number of whole days since 1970-01-01 in UTC
+pub fn get_current_day() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| (duration.as_secs() / 86400) as i64)
+}
+
+// Format a number of days since 1970-01-01 as YYYY-MM-DD
+pub fn format_day(day: i64) -> String {
+    // Converts days to a date in the proleptic Gregorian calendar, counting years from March so
+    // that leap days fall at the end of the year
+    let z = day + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!("{year:04}-{month:02}-{day_of_month:02}")
+}
+
 #[derive(Bundle)]
 pub struct AnimatedSprite {
     animation_handle: Handle<SpriteSheetAnimation>,
@@ -72,3 +103,17 @@ impl Default for AnimatedSpriteData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_day_gives_dates() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(11016), "2000-02-29");
+        assert_eq!(format_day(19000), "2022-01-08");
+        assert_eq!(format_day(-1), "1969-12-31");
+        assert_eq!(format_day(-25567), "1900-01-01");
+    }
+}
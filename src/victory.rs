@@ -1,94 +1,298 @@
-use crate::{ui::GameFont, AppState};
+use crate::{
+    assist::AssistSettings,
+    collectible::RunProgress,
+    daily::{get_daily_leaderboard, DailyRun},
+    game_overlay::GameTimer,
+    leaderboard::{Leaderboard, LeaderboardEntry, LeaderboardLevel, PlayerName},
+    level_pack::{LevelPacks, SelectedPack},
+    online::{backend::Submission, OnlineLeaderboard},
+    records::{LevelRecords, RunResult},
+    replay::Replay,
+    score::get_score,
+    ui::{
+        widgets::{
+            spawn_button_list, spawn_column, spawn_menu_screen, spawn_row, spawn_text,
+            spawn_text_input, spawn_title, TextInput,
+        },
+        GameFont,
+    },
+    upgrades::UpgradeTracker,
+    world::{GameWorld, WorldType},
+    AppState,
+};
 use bevy::prelude::*;
 
 pub struct VictoryPlugin;
 
 impl Plugin for VictoryPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(SystemSet::on_enter(AppState::Victory).with_system(create_victory_menu))
+        app.init_resource::<LevelRecords>()
+            .add_system_set(SystemSet::on_enter(AppState::Victory).with_system(create_victory_menu))
             .add_system_set(
-                SystemSet::on_update(AppState::Victory).with_system(manage_menu_button),
-            );
+                SystemSet::on_update(AppState::Victory).with_system(manage_victory_buttons),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Victory).with_system(submit_online));
     }
 }
 
 #[derive(Component)]
-struct MenuButton;
-
-fn create_victory_menu(mut commands: Commands, font: Res<GameFont>) {
-    commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
-                ..Style::default()
-            },
-            color: Color::NONE.into(),
-            ..NodeBundle::default()
-        })
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    position: Rect {
-                        top: Val::Percent(20.0),
-                        ..Rect::default()
-                    },
-                    ..Style::default()
-                },
-                text: Text::with_section(
-                    "Victory!",
-                    TextStyle {
-                        font: font.get_handle(),
-                        font_size: 120.0,
-                        ..TextStyle::default()
-                    },
-                    TextAlignment::default(),
-                ),
-                ..TextBundle::default()
-            });
-
-            parent
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        position_type: PositionType::Absolute,
-                        position: Rect {
-                            top: Val::Percent(40.0),
-                            ..Rect::default()
-                        },
-                        size: Size::new(Val::Px(300.0), Val::Px(65.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Style::default()
-                    },
-                    ..ButtonBundle::default()
-                })
-                .insert(MenuButton)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        text: Text::with_section(
-                            "Menu",
-                            TextStyle {
-                                font: font.get_handle(),
-                                font_size: 60.0,
-                                color: Color::BLACK,
-                            },
-                            TextAlignment::default(),
-                        ),
-                        ..TextBundle::default()
-                    });
+enum ButtonType {
+    NextLevel,
+    SaveScore,
+    Leaderboard,
+    Menu,
+}
+
+// Run that made it onto the level's leaderboard, waiting for the player to enter their name
+struct UnsavedEntry(LeaderboardEntry);
+
+#[derive(Component)]
+struct NameInput;
+
+// Run to submit to the online leaderboard when leaving the victory screen, once the player has had
+// the chance to enter their name
+struct PendingSubmission(Submission);
+
+fn create_victory_menu(
+    mut commands: Commands,
+    font: Res<GameFont>,
+    world: Res<GameWorld>,
+    packs: Res<LevelPacks>,
+    progress: Res<RunProgress>,
+    assists: Res<AssistSettings>,
+    upgrades: Res<UpgradeTracker>,
+    player_name: Res<PlayerName>,
+    online: Res<OnlineLeaderboard>,
+    replay: Res<Replay>,
+    daily_run: Option<Res<DailyRun>>,
+    mut records: ResMut<LevelRecords>,
+    game_timer: Query<&GameTimer>,
+) {
+    let time = game_timer.single().elapsed_secs();
+    let (nectar, total_nectar) = (progress.nectar.len(), world.get_nectar_count());
+
+    let score = get_score(time, &progress);
+    let mut results = format!("Time {time:.2}");
+    if total_nectar > 0 {
+        results += &format!("   Nectar {nectar}/{total_nectar}");
+    }
+    results += &format!("   Score {score}");
+    // Assisted runs are recorded separately and can't earn medals
+    let assisted = assists.is_active();
+    if assisted {
+        results += "   Assisted";
+    }
+    let mut has_next_level = false;
+    let mut medal = None;
+    let mut has_leaderboard = false;
+    let mut qualifies = false;
+    if let WorldType::Level { pack, index } = world.world_type {
+        let level_pack = &packs.0[pack];
+        let level_id = level_pack.get_level_id(index);
+        let result = RunResult {
+            time,
+            nectar,
+            score,
+            assisted,
+            level_hash: world.get_level_hash(),
+        };
+        if records.record(level_id.clone(), &result) {
+            results += "   New best!";
+        }
+        medal = level_pack.levels[index]
+            .medal_times
+            .filter(|_| !assisted)
+            .and_then(|medal_times| medal_times.get_medal(time));
+        // Completing a level unlocks the next one in its pack
+        has_next_level = index + 1 < level_pack.levels.len();
+
+        // Assisted runs are kept off the leaderboard like they are kept from best times
+        has_leaderboard = true;
+        qualifies = !assisted && Leaderboard::load(&level_id).qualifies(time);
+        if qualifies {
+            commands.insert_resource(UnsavedEntry(LeaderboardEntry::new(
+                time,
+                score,
+                &upgrades,
+                world.get_level_hash(),
+            )));
+        }
+        if !assisted && online.is_enabled() {
+            commands.insert_resource(PendingSubmission(Submission {
+                level_hash: world.get_level_hash(),
+                name: String::new(),
+                time,
+                replay: replay.to_bytes(),
+            }));
+        }
+    }
+    if let WorldType::Daily { seed } = world.world_type {
+        has_leaderboard = true;
+        // The scored attempt goes on the leaderboard straight away under the name it was
+        // started with
+        let mut leaderboard = get_daily_leaderboard(seed, None);
+        match daily_run {
+            Some(run) if run.scored => {
+                if !assisted {
+                    let entry = LeaderboardEntry {
+                        name: run.name.clone(),
+                        ..LeaderboardEntry::new(time, score, &upgrades, world.get_level_hash())
+                    };
+                    leaderboard.highlight =
+                        Leaderboard::load(&leaderboard.level_id).add(&leaderboard.level_id, entry);
+                }
+            }
+            _ => results += "   Practice",
+        }
+        // Shown by the leaderboard button
+        commands.insert_resource(leaderboard);
+    }
+
+    let mut buttons = Vec::new();
+    if qualifies {
+        buttons.push((ButtonType::SaveScore, "Save Score"));
+    }
+    if has_next_level {
+        buttons.push((ButtonType::NextLevel, "Next Level"));
+    }
+    if has_leaderboard {
+        buttons.push((ButtonType::Leaderboard, "Leaderboard"));
+    }
+    buttons.push((ButtonType::Menu, "Main Menu"));
+
+    spawn_menu_screen(&mut commands, &font, |parent| {
+        spawn_title(parent, &font, "Victory!");
+        spawn_column(parent, |parent| {
+            spawn_text(
+                parent,
+                &font,
+                50.0,
+                [
+                    (results, Color::WHITE),
+                    // Show the medal earned by this run in its color
+                    (
+                        medal.map_or_else(String::new, |medal| format!("   {medal}")),
+                        medal.map_or(Color::WHITE, |medal| medal.color()),
+                    ),
+                ],
+            );
+            spawn_text(
+                parent,
+                &font,
+                30.0,
+                [(
+                    format!("Level hash {}", world.get_level_hash()),
+                    Color::GRAY,
+                )],
+            );
+            if qualifies {
+                // The last name entered is suggested, but the machine may be shared so it isn't
+                // filled in
+                spawn_row(parent, &font, "Name", |parent| {
+                    spawn_text_input(parent, &font, player_name.get_placeholder(), true)
+                        .insert(NameInput);
                 });
+            }
+            spawn_button_list(parent, &font, buttons);
         });
+    });
 }
 
-fn manage_menu_button(
+fn manage_victory_buttons(
+    mut commands: Commands,
     mut state: ResMut<State<AppState>>,
-    interaction: Query<&Interaction, (Changed<Interaction>, With<Button>, With<MenuButton>)>,
+    world: Res<GameWorld>,
+    packs: Res<LevelPacks>,
+    unsaved_entry: Option<Res<UnsavedEntry>>,
+    mut player_name: ResMut<PlayerName>,
+    name_input: Query<&TextInput, With<NameInput>>,
+    interaction: Query<(&Interaction, &ButtonType), (Changed<Interaction>, With<Button>)>,
 ) {
-    for interaction in interaction.iter() {
-        if matches!(interaction, Interaction::Clicked) {
-            state.set(AppState::Menu).unwrap();
+    for (interaction, button_type) in interaction.iter() {
+        match (interaction, button_type) {
+            (Interaction::Clicked, ButtonType::NextLevel) => {
+                if let WorldType::Level { pack, index } = world.world_type {
+                    match GameWorld::load_level(&packs, pack, index + 1) {
+                        Ok(world) => {
+                            commands.insert_resource(world);
+                            commands.insert_resource(RunProgress::default());
+                            state.set(AppState::StartDelay).unwrap();
+                        }
+                        Err(error) => error!("Failed to load level: {error}"),
+                    }
+                }
+            }
+            (Interaction::Clicked, ButtonType::SaveScore) => {
+                if let (WorldType::Level { pack, index }, Some(entry)) =
+                    (&world.world_type, &unsaved_entry)
+                {
+                    let name = get_entered_name(&name_input, &player_name);
+                    player_name.set(name.clone());
+
+                    let level_id = packs.0[*pack].get_level_id(*index);
+                    let highlight = Leaderboard::load(&level_id).add(
+                        &level_id,
+                        LeaderboardEntry {
+                            name,
+                            ..entry.0.clone()
+                        },
+                    );
+                    show_leaderboard(&mut commands, &mut state, &packs, *pack, *index, highlight);
+                }
+            }
+            (Interaction::Clicked, ButtonType::Leaderboard) => match world.world_type {
+                WorldType::Level { pack, index } => {
+                    show_leaderboard(&mut commands, &mut state, &packs, pack, index, None);
+                }
+                WorldType::Daily { .. } => state.set(AppState::Leaderboard).unwrap(),
+                WorldType::Endless => {}
+            },
+            (Interaction::Clicked, ButtonType::Menu) => {
+                state.set(AppState::Menu).unwrap();
+            }
+            _ => {}
         }
     }
 }
+
+fn show_leaderboard(
+    commands: &mut Commands,
+    state: &mut State<AppState>,
+    packs: &LevelPacks,
+    pack: usize,
+    index: usize,
+    highlight: Option<usize>,
+) {
+    // Going back from the leaderboard leads to the level's pack
+    commands.insert_resource(SelectedPack(pack));
+    commands.insert_resource(LeaderboardLevel::for_pack_level(
+        packs, pack, index, highlight,
+    ));
+    state.set(AppState::Leaderboard).unwrap();
+}
+
+fn get_entered_name(
+    name_input: &Query<&TextInput, With<NameInput>>,
+    player_name: &PlayerName,
+) -> String {
+    let typed = name_input
+        .get_single()
+        .map_or("", |input| input.value.as_str());
+    player_name.get_entered(typed)
+}
+
+fn submit_online(
+    mut commands: Commands,
+    online: Res<OnlineLeaderboard>,
+    pending: Option<Res<PendingSubmission>>,
+    player_name: Res<PlayerName>,
+    name_input: Query<&TextInput, With<NameInput>>,
+) {
+    if let Some(pending) = pending {
+        online.submit(Submission {
+            name: get_entered_name(&name_input, &player_name),
+            ..pending.0.clone()
+        });
+        commands.remove_resource::<PendingSubmission>();
+    }
+}
@@ -1,25 +1,38 @@
 use crate::{
+    accessibility::Hazard,
+    assist::GameTime,
     camera,
+    collectible::{Checkpoint, LockedGoal, Nectar, RunProgress},
+    collision::Hitbox,
     enemy::{Enemy, Projectile, Wall},
-    player,
+    level_pack::LevelPacks,
+    medal::MedalTimes,
+    player::{self, Player},
+    settings::Settings,
+    sound::{PlaySound, SoundEffect},
     upgrades::UpgradeTracker,
     util::{AnimatedSprite, AnimatedSpriteData},
     AppState,
 };
 use benimator::SpriteSheetAnimation;
 use bevy::prelude::*;
-use impacted::CollisionShape;
 use std::f32::consts::PI;
 use std::io;
+use std::time::SystemTime;
+
+// Seconds between checks for changes to the file of the level being played
+const RELOAD_CHECK_INTERVAL: f64 = 0.5;
 
 pub enum WorldType {
-    Level { index: usize },
+    Level { pack: usize, index: usize },
     Endless,
+    // Generated level of the daily challenge
+    Daily { seed: u64 },
 }
 
 #[derive(Component, Clone, Debug)]
 pub struct Spawner {
-    projectile: Projectile,
+    pub projectile: Projectile,
     timer: Timer,
 }
 
@@ -35,6 +48,12 @@ impl Spawner {
             timer: Timer::from_seconds(cooldown, true),
         }
     }
+
+    // Seconds until the next projectile is spawned
+    #[cfg(feature = "debug-overlay")]
+    pub fn get_remaining_secs(&self) -> f32 {
+        self.timer.duration().as_secs_f32() - self.timer.elapsed_secs()
+    }
 }
 
 #[derive(Debug)]
@@ -42,7 +61,10 @@ pub enum Tile {
     Wall,
     Spawner(Spawner),
     Trap,
-    Goal,
+    // A locked goal can only be reached once every nectar has been collected
+    Goal { locked: bool },
+    Checkpoint,
+    Nectar,
 }
 
 impl Tile {
@@ -52,75 +74,206 @@ impl Tile {
 #[derive(Component)]
 pub struct Goal;
 
+// Marks the entities spawned for the tiles of the level, which are replaced when it is reloaded
+#[derive(Component)]
+struct LevelTile;
+
+// Sent when the level being played has been reloaded from its file
+pub struct LevelReloaded;
+
 pub struct GameWorld {
     pub world_type: WorldType,
     // Coordinates of the player's spawn location: (x, y)
     pub player_start_coordinates: (usize, usize),
     pub layout: Vec<Vec<Option<Tile>>>,
+    // Computed once when the level is loaded, see compute_content_hash
+    content_hash: u64,
+    // When the file the level was loaded from was last changed, used to reload it when it changes
+    source_modified: Option<SystemTime>,
 }
 
-pub const LEVELS: [(&str, &str); 8] = [
+// Levels built into the game: (name, file name in assets/levels, contents, medal times)
+pub const LEVELS: [(&str, &str, &str, MedalTimes); 8] = [
     (
         "Closing Doors",
+        "Levels_-_Beeline_-_Closing_Doors.tsv",
         include_str!("../assets/levels/Levels_-_Beeline_-_Closing_Doors.tsv"),
+        MedalTimes::new(27.0, 18.0, 11.0, 7.0),
+    ),
+    (
+        "Cornered",
+        "cornered.tsv",
+        include_str!("../assets/levels/cornered.tsv"),
+        MedalTimes::new(13.0, 8.5, 5.5, 3.5),
     ),
-    ("Cornered", include_str!("../assets/levels/cornered.tsv")),
     (
         "Serpentine",
+        "Beeline_-_Serpentine.tsv",
         include_str!("../assets/levels/Beeline_-_Serpentine.tsv"),
+        MedalTimes::new(14.0, 9.5, 6.0, 3.5),
     ),
     (
         "Snakes on a Plane",
+        "snakes-on-a-plane.tsv",
         include_str!("../assets/levels/snakes-on-a-plane.tsv"),
+        MedalTimes::new(8.5, 6.0, 3.5, 2.5),
+    ),
+    (
+        "Chicken",
+        "chicken.tsv",
+        include_str!("../assets/levels/chicken.tsv"),
+        MedalTimes::new(12.0, 8.0, 5.0, 3.0),
+    ),
+    (
+        "Maze",
+        "maze.tsv",
+        include_str!("../assets/levels/maze.tsv"),
+        MedalTimes::new(30.0, 20.0, 12.5, 7.5),
     ),
-    ("Chicken", include_str!("../assets/levels/chicken.tsv")),
-    ("Maze", include_str!("../assets/levels/maze.tsv")),
     (
         "Down The Road",
+        "down-the-road.tsv",
         include_str!("../assets/levels/down-the-road.tsv"),
+        MedalTimes::new(23.0, 15.0, 9.5, 6.0),
     ),
     (
         "Drift",
+        "Levels_-_Beeline_-_Drift.tsv",
         include_str!("../assets/levels/Levels_-_Beeline_-_Drift.tsv"),
+        MedalTimes::new(24.0, 16.0, 10.0, 6.0),
     ),
 ];
 
 impl GameWorld {
-    pub fn load_level(level: usize) -> io::Result<Self> {
-        // Open file and collect rows
-        let lines = LEVELS[level].1.lines();
+    pub fn load_level(packs: &LevelPacks, pack: usize, index: usize) -> io::Result<Self> {
+        let source = &packs.get_level(pack, index).source;
+        let modified = source.get_modified();
+        let mut world = Self::parse(&source.read()?, WorldType::Level { pack, index })?;
+        world.source_modified = modified;
+        Ok(world)
+    }
+
+    pub fn parse(contents: &str, world_type: WorldType) -> io::Result<Self> {
+        let invalid_value = |value: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid value: {value}"),
+            )
+        };
 
         let mut start = None;
         let mut layout = Vec::new();
-        for (i, line) in lines.enumerate() {
+        for (i, line) in contents.lines().enumerate() {
             let mut row = Vec::new();
             for (j, value) in line.split('\t').enumerate() {
-                let tile = value.chars().next().and_then(|char| match char {
-                    '.' => None,
-                    '#' => Some(Tile::Wall),
-                    'L' => Some(Tile::Spawner(Spawner::new(Projectile::Laser {
-                        angle: (&value[2..]).parse::<f32>().unwrap(),
+                let tile = match value.chars().next() {
+                    None | Some('.') => None,
+                    Some('#') => Some(Tile::Wall),
+                    Some('L') => Some(Tile::Spawner(Spawner::new(Projectile::Laser {
+                        angle: value
+                            .get(2..)
+                            .and_then(|angle| angle.parse::<f32>().ok())
+                            .ok_or_else(|| invalid_value(value))?,
                     }))),
-                    'M' => Some(Tile::Spawner(Spawner::new(Projectile::Missile))),
-                    'T' => Some(Tile::Trap),
-                    'G' => Some(Tile::Goal),
-                    '*' => {
+                    Some('M') => Some(Tile::Spawner(Spawner::new(Projectile::Missile))),
+                    Some('T') => Some(Tile::Trap),
+                    Some('G') => Some(Tile::Goal {
+                        locked: value.get(2..) == Some("N"),
+                    }),
+                    Some('C') => Some(Tile::Checkpoint),
+                    Some('N') => Some(Tile::Nectar),
+                    Some('*') => {
                         // The * character indicates player's spawn location
                         start = Some((j, i));
                         None
                     }
-                    _ => panic!("Invalid value: {value}"),
-                });
+                    Some(_) => return Err(invalid_value(value)),
+                };
                 row.push(tile);
             }
             layout.push(row);
         }
 
-        Ok(Self {
-            world_type: WorldType::Level { index: level },
+        let mut world = Self {
+            world_type,
             player_start_coordinates: start.unwrap_or((0, 0)),
             layout,
-        })
+            content_hash: 0,
+            source_modified: None,
+        };
+        world.content_hash = world.compute_content_hash();
+        Ok(world)
+    }
+
+    // Hash of everything that affects how the level plays, which is the same for levels that only
+    // differ in formatting, such as trailing empty cells
+    fn compute_content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::default();
+        let (start_x, start_y) = self.player_start_coordinates;
+        hasher.write(&(start_x as u64).to_le_bytes());
+        hasher.write(&(start_y as u64).to_le_bytes());
+
+        let rows = self
+            .layout
+            .iter()
+            .rposition(|row| row.iter().any(Option::is_some))
+            .map_or(0, |last| last + 1);
+        for row in &self.layout[..rows] {
+            let columns = row
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |last| last + 1);
+            for tile in &row[..columns] {
+                match tile {
+                    None => hasher.write(&[0]),
+                    Some(Tile::Wall) => hasher.write(&[1]),
+                    Some(Tile::Spawner(spawner)) => match spawner.projectile {
+                        Projectile::Missile => hasher.write(&[2]),
+                        Projectile::Laser { angle } => {
+                            hasher.write(&[3]);
+                            hasher.write(&angle.to_le_bytes());
+                        }
+                    },
+                    Some(Tile::Trap) => hasher.write(&[4]),
+                    Some(Tile::Goal { locked }) => hasher.write(&[5, *locked as u8]),
+                    Some(Tile::Checkpoint) => hasher.write(&[6]),
+                    Some(Tile::Nectar) => hasher.write(&[7]),
+                }
+            }
+            // End of the row
+            hasher.write(&[u8::MAX]);
+        }
+        hasher.0
+    }
+
+    // Content hash as shown to players and stored with records, which tells versions of a level
+    // apart
+    pub fn get_level_hash(&self) -> String {
+        format!("{:016x}", self.content_hash)
+    }
+
+    fn get_tile(&self, (x, y): (usize, usize)) -> Option<&Tile> {
+        self.layout.get(y)?.get(x)?.as_ref()
+    }
+
+    pub fn get_nectar_count(&self) -> usize {
+        self.layout
+            .iter()
+            .flatten()
+            .filter(|tile| matches!(tile, Some(Tile::Nectar)))
+            .count()
+    }
+
+    // Corners of the area covered by the layout in world coordinates: (bottom left, top right)
+    pub fn get_bounds(&self) -> (Vec2, Vec2) {
+        let height = self.layout.len() as f32;
+        let width = self.layout.iter().map(Vec::len).max().unwrap_or(0) as f32;
+        // Tiles are centered on their coordinates, with rows going downwards
+        let half_tile = Tile::SIZE / 2.0;
+        (
+            Vec2::new(-half_tile, -(height - 1.0) * Tile::SIZE - half_tile),
+            Vec2::new((width - 1.0) * Tile::SIZE + half_tile, half_tile),
+        )
     }
 
     fn get_wall_neighbors(&self, x: usize, y: usize) -> [bool; 4] {
@@ -145,27 +298,77 @@ impl GameWorld {
 
         neighbors
     }
+
+    // Returns the name of the wall texture that connects to the neighboring walls
+    pub fn get_wall_texture_name(&self, x: usize, y: usize) -> &'static str {
+        match self.get_wall_neighbors(x, y) {
+            [true, false, true, false] => "wewe",
+            [false, false, true, false] => "eewe",
+            [true, false, true, true] => "weww",
+            [true, true, true, false] => "wwwe",
+            [false, true, false, true] => "ewew",
+            [false, false, false, true] => "eeew",
+            [false, true, false, false] => "ewee",
+            [true, true, false, true] => "wwew",
+            [true, true, true, true] => "wwww",
+            [true, false, false, false] => "weee",
+            [true, false, false, true] => "weew",
+            [true, true, false, false] => "wwee",
+            [false, true, true, true] => "ewww",
+            [false, true, true, false] => "ewwe",
+            [false, false, true, true] => "eeww",
+            [false, false, false, false] => "eeee",
+        }
+    }
+}
+
+// 64 bit FNV-1a, which unlike the standard library's hasher is the same on every platform and
+// version, so hashes can be compared between players
+struct ContentHasher(u64);
+
+impl ContentHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
 }
 
+// Label of spawn_world, for systems that must run before the world is spawned
+pub const SPAWN_WORLD: &str = "spawn_world";
+
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(spawn_world))
-            .add_system_set(SystemSet::on_update(AppState::Game).with_system(spawn_projectiles));
+        app.add_event::<LevelReloaded>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(spawn_world))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(spawn_projectiles)
+                    .with_system(reload_level),
+            );
     }
 }
 
-pub fn spawn_world(
-    mut commands: Commands,
-    world: Res<GameWorld>,
-    mut animations: ResMut<Assets<SpriteSheetAnimation>>,
-    mut textures: ResMut<Assets<TextureAtlas>>,
-    asset_server: Res<AssetServer>,
-    upgrades: Res<UpgradeTracker>,
-) {
+// Spawn an entity for every tile of the level, returns the position of the goal
+fn spawn_tiles(
+    commands: &mut Commands,
+    world: &GameWorld,
+    animations: &mut ResMut<Assets<SpriteSheetAnimation>>,
+    textures: &mut ResMut<Assets<TextureAtlas>>,
+    asset_server: &Res<AssetServer>,
+    progress: &RunProgress,
+) -> Option<Vec2> {
     let tile_size = Vec2::splat(Tile::SIZE);
     let mut goal_position = None;
+    let goals_locked = progress.nectar.len() < world.get_nectar_count();
 
     // Iterate through the world layout and spawn tiles accordingly
     for (i, row) in world.layout.iter().enumerate() {
@@ -174,26 +377,7 @@ pub fn spawn_world(
                 Transform::from_xyz(j as f32 * Tile::SIZE, -(i as f32 * Tile::SIZE), 0.0);
             match tile {
                 Some(Tile::Wall) => {
-                    let neighbors = world.get_wall_neighbors(j, i);
-                    let name = match neighbors {
-                        [true, false, true, false] => "wewe",
-                        [false, false, true, false] => "eewe",
-                        [true, false, true, true] => "weww",
-                        [true, true, true, false] => "wwwe",
-                        [false, true, false, true] => "ewew",
-                        [false, false, false, true] => "eeew",
-                        [false, true, false, false] => "ewee",
-                        [true, true, false, true] => "wwew",
-                        [true, true, true, true] => "wwww",
-                        [true, false, false, false] => "weee",
-                        [true, false, false, true] => "weew",
-                        [true, true, false, false] => "wwee",
-                        [false, true, true, true] => "ewww",
-                        [false, true, true, false] => "ewwe",
-                        [false, false, true, true] => "eeww",
-                        [false, false, false, false] => "eeee",
-                    };
-
+                    let name = world.get_wall_texture_name(j, i);
                     let path = format!("walls/{name}.png");
                     commands
                         .spawn_bundle(SpriteBundle {
@@ -205,9 +389,10 @@ pub fn spawn_world(
                             transform,
                             ..SpriteBundle::default()
                         })
-                        .insert(CollisionShape::new_rectangle(tile_size.x, tile_size.y))
+                        .insert(Hitbox::Rectangle(tile_size))
                         .insert(Wall)
-                        .insert(Enemy);
+                        .insert(Enemy)
+                        .insert(LevelTile);
                 }
                 Some(Tile::Spawner(spawner)) => match spawner.projectile {
                     Projectile::Missile => {
@@ -221,14 +406,15 @@ pub fn spawn_world(
                                 transform,
                                 ..SpriteBundle::default()
                             })
-                            .insert(spawner.clone());
+                            .insert(spawner.clone())
+                            .insert(LevelTile);
                     }
                     Projectile::Laser { angle, .. } => {
                         commands
                             .spawn_bundle(AnimatedSprite::new(
-                                &mut animations,
-                                &mut textures,
-                                &asset_server,
+                                animations,
+                                textures,
+                                asset_server,
                                 AnimatedSpriteData {
                                     path: "laser-spawner.png".into(),
                                     frames: 2,
@@ -241,15 +427,16 @@ pub fn spawn_world(
                                     ..AnimatedSpriteData::default()
                                 },
                             ))
-                            .insert(spawner.clone());
+                            .insert(spawner.clone())
+                            .insert(LevelTile);
                     }
                 },
                 Some(Tile::Trap) => {
                     commands
                         .spawn_bundle(AnimatedSprite::new(
-                            &mut animations,
-                            &mut textures,
-                            &asset_server,
+                            animations,
+                            textures,
+                            asset_server,
                             AnimatedSpriteData {
                                 path: "trap.png".into(),
                                 frames: 6,
@@ -258,37 +445,101 @@ pub fn spawn_world(
                                 ..AnimatedSpriteData::default()
                             },
                         ))
-                        .insert(CollisionShape::new_rectangle(tile_size.x, tile_size.y))
-                        .insert(Enemy);
+                        .insert(Hitbox::Rectangle(tile_size))
+                        .insert(Hazard::Trap)
+                        .insert(Enemy)
+                        .insert(LevelTile);
+                }
+                Some(Tile::Goal { locked }) => {
+                    let mut goal = commands.spawn_bundle(AnimatedSprite::new(
+                        animations,
+                        textures,
+                        asset_server,
+                        AnimatedSpriteData {
+                            path: "goal.png".into(),
+                            frames: 6,
+                            size: tile_size,
+                            transform,
+                            ..AnimatedSpriteData::default()
+                        },
+                    ));
+                    goal.insert(Hitbox::Rectangle(tile_size))
+                        .insert(Goal)
+                        .insert(LevelTile);
+                    if *locked && goals_locked {
+                        goal.insert(LockedGoal);
+                    }
+                    goal_position = Some(transform.translation.truncate());
                 }
-                Some(Tile::Goal) => {
+                Some(Tile::Checkpoint) => {
                     commands
-                        .spawn_bundle(AnimatedSprite::new(
-                            &mut animations,
-                            &mut textures,
-                            &asset_server,
-                            AnimatedSpriteData {
-                                path: "goal.png".into(),
-                                frames: 6,
-                                size: tile_size,
-                                transform,
-                                ..AnimatedSpriteData::default()
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                color: Checkpoint::color(progress.checkpoint == Some((j, i))),
+                                custom_size: Some(tile_size),
+                                ..Sprite::default()
                             },
-                        ))
-                        .insert(CollisionShape::new_rectangle(tile_size.x, tile_size.y))
-                        .insert(Goal);
-                    goal_position = Some(transform.translation.truncate());
+                            texture: asset_server.load("checkpoint.png"),
+                            transform,
+                            ..SpriteBundle::default()
+                        })
+                        .insert(Hitbox::Rectangle(tile_size))
+                        .insert(Checkpoint {
+                            coordinates: (j, i),
+                        })
+                        .insert(LevelTile);
+                }
+                Some(Tile::Nectar) => {
+                    // Nectar that has already been collected is not respawned
+                    if !progress.nectar.contains(&(j, i)) {
+                        commands
+                            .spawn_bundle(SpriteBundle {
+                                sprite: Sprite {
+                                    custom_size: Some(tile_size),
+                                    ..Sprite::default()
+                                },
+                                texture: asset_server.load("nectar.png"),
+                                transform,
+                                ..SpriteBundle::default()
+                            })
+                            .insert(Hitbox::Rectangle(Vec2::splat(Nectar::SIZE)))
+                            .insert(Nectar {
+                                coordinates: (j, i),
+                            })
+                            .insert(LevelTile);
+                    }
                 }
                 None => {}
             }
         }
     }
+    goal_position
+}
 
-    // Convert player start coordinates into world position
-    let player_start_location = Vec2::new(
-        world.player_start_coordinates.0 as f32,
-        -(world.player_start_coordinates.1 as f32),
-    ) * Tile::SIZE;
+pub fn spawn_world(
+    mut commands: Commands,
+    world: Res<GameWorld>,
+    mut animations: ResMut<Assets<SpriteSheetAnimation>>,
+    mut textures: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    upgrades: Res<UpgradeTracker>,
+    progress: Res<RunProgress>,
+    settings: Res<Settings>,
+) {
+    let goal_position = spawn_tiles(
+        &mut commands,
+        &world,
+        &mut animations,
+        &mut textures,
+        &asset_server,
+        &progress,
+    );
+
+    // Convert player start coordinates into world position, respawning at the last checkpoint reached
+    let start_coordinates = progress
+        .checkpoint
+        .unwrap_or(world.player_start_coordinates);
+    let player_start_location = to_world_position(start_coordinates);
 
     // Spawn the player
     player::spawn_player(
@@ -300,7 +551,89 @@ pub fn spawn_world(
         player_start_location,
     );
 
-    camera::spawn_camera(&mut commands, goal_position.unwrap_or(Vec2::ZERO));
+    camera::spawn_camera(
+        &mut commands,
+        goal_position.unwrap_or(Vec2::ZERO),
+        settings.get_camera_scale(),
+    );
+}
+
+// Position of a tile in the world, where y goes up as the row number goes down
+fn to_world_position((x, y): (usize, usize)) -> Vec2 {
+    Vec2::new(x as f32, -(y as f32)) * Tile::SIZE
+}
+
+// Rebuild the level in place when its file changes, so that edits can be tried out without leaving
+// the level
+fn reload_level(
+    mut commands: Commands,
+    mut world: ResMut<GameWorld>,
+    packs: Res<LevelPacks>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    mut last_check: Local<f64>,
+    mut progress: ResMut<RunProgress>,
+    mut animations: ResMut<Assets<SpriteSheetAnimation>>,
+    mut textures: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    mut reloaded: EventWriter<LevelReloaded>,
+    tiles: Query<Entity, With<LevelTile>>,
+    mut player: Query<&mut Transform, With<Player>>,
+) {
+    // Only levels in packs can be loaded from files
+    let (pack, index) = match world.world_type {
+        WorldType::Level { pack, index } => (pack, index),
+        _ => return,
+    };
+    if time.seconds_since_startup() - *last_check < RELOAD_CHECK_INTERVAL {
+        return;
+    }
+    *last_check = time.seconds_since_startup();
+
+    let modified = packs.get_level(pack, index).source.get_modified();
+    if modified.is_none() || modified == world.source_modified {
+        return;
+    }
+    match GameWorld::load_level(&packs, pack, index) {
+        Ok(new_world) => *world = new_world,
+        Err(error) => {
+            // Keep playing the last version that loaded and wait for the file to change again
+            warn!("Failed to reload level: {error}");
+            world.source_modified = modified;
+            return;
+        }
+    }
+
+    // Forget progress on nectar and checkpoints that are no longer there
+    progress
+        .nectar
+        .retain(|coordinates| matches!(world.get_tile(*coordinates), Some(Tile::Nectar)));
+    let checkpoint_removed = progress.checkpoint.map_or(false, |coordinates| {
+        !matches!(world.get_tile(coordinates), Some(Tile::Checkpoint))
+    });
+    if checkpoint_removed {
+        progress.checkpoint = None;
+    }
+
+    for entity in tiles.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_tiles(
+        &mut commands,
+        &world,
+        &mut animations,
+        &mut textures,
+        &asset_server,
+        &progress,
+    );
+
+    if !settings.keep_position_on_reload {
+        let start = to_world_position(world.player_start_coordinates);
+        for mut transform in player.iter_mut() {
+            transform.translation = start.extend(transform.translation.z);
+        }
+    }
+    reloaded.send(LevelReloaded);
 }
 
 fn spawn_projectiles(
@@ -308,7 +641,8 @@ fn spawn_projectiles(
     mut animations: ResMut<Assets<SpriteSheetAnimation>>,
     mut textures: ResMut<Assets<TextureAtlas>>,
     asset_server: Res<AssetServer>,
-    time: Res<Time>,
+    time: Res<GameTime>,
+    mut sounds: EventWriter<PlaySound>,
     mut spawners: Query<(&Transform, &mut Spawner)>,
 ) {
     for (spawner_transform, mut spawner) in spawners.iter_mut() {
@@ -323,6 +657,12 @@ fn spawn_projectiles(
                 &asset_server,
                 spawn_position,
             );
+
+            let effect = match spawner.projectile {
+                Projectile::Missile => SoundEffect::MissileLaunch,
+                Projectile::Laser { .. } => SoundEffect::LaserFire,
+            };
+            sounds.send(PlaySound::at(effect, spawn_position));
         }
     }
 }